@@ -9,7 +9,7 @@
  */
 
 use aubio_lib as _;
-use aubio_rs::Notes;
+use aubio_rs::{Hops, NoteEvent, NoteTracker, Notes};
 use hound::WavReader;
 use std::io::stdin;
 
@@ -22,40 +22,37 @@ fn main() {
     let input = stdin();
     let mut reader = WavReader::new(input).unwrap();
     let format = reader.spec();
-
-    let mut samples = reader.samples();
-    let mut notes = Notes::new(BUF_SIZE, HOP_SIZE, format.sample_rate).unwrap();
-
-    let period = 1.0 / format.sample_rate as f32;
-
-    let mut time = 0.0;
-    let mut offset = 0;
-
-    loop {
-        let block = samples
-            .by_ref()
-            .map(|sample| sample.map(|sample: i16| sample as f32 * I16_TO_F32))
-            .take(HOP_SIZE)
-            .collect::<Result<Vec<f32>, _>>()
-            .unwrap();
-
-        if block.len() == HOP_SIZE {
-            for note in notes.do_result(block.as_slice().as_ref()).unwrap() {
-                if note.velocity > 0.0 {
-                    print!("{}\t{}\t", note.pitch, time);
-                } else {
-                    println!("{}", time);
-                }
-            }
+    let duration = reader.duration();
+
+    let samples = reader.samples().map(|sample| {
+        sample
+            .map(|sample: i16| sample as f32 * I16_TO_F32)
+            .unwrap()
+    });
+    let notes = Notes::new(BUF_SIZE, HOP_SIZE, format.sample_rate).unwrap();
+    let to_seconds = |position: usize| position as f32 / format.sample_rate as f32;
+    let print_note = |note: NoteEvent| {
+        println!(
+            "{}\t{}\t{}",
+            note.midi_note,
+            to_seconds(note.start),
+            to_seconds(note.end)
+        )
+    };
+
+    let mut notes = Hops::new(samples, HOP_SIZE, format.sample_rate)
+        .process_with(NoteTracker::new(notes))
+        .unwrap();
+
+    for note in notes.by_ref() {
+        if let Some(note) = note.unwrap().value {
+            print_note(note);
         }
+    }
 
-        offset += block.len();
-        time = offset as f32 * period;
-
-        if block.len() < HOP_SIZE {
-            break;
-        }
+    if let Some(note) = notes.into_processor().finish() {
+        print_note(note);
     }
 
-    println!("{}", time);
+    println!("{}", to_seconds(duration as usize));
 }
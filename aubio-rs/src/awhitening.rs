@@ -0,0 +1,179 @@
+use crate::{check_init, ffi, vec::CVecMut, Result, Status};
+
+/**
+ * Spectral adaptive whitening object
+ *
+ * This object computes the adaptive whitening of a spectral frame,
+ * by dividing each magnitude by a slowly decaying peak estimate.
+ *
+ * It is the same normalization `Onset` applies when `with_awhitening(true)`
+ * is used, and can be applied to spectra before `SpecDesc` or `FilterBank`.
+ *
+ * References:
+ *
+ * D. Stowell and M. D. Plumbley. Adaptive whitening for improved real-time
+ * audio onset detection. In Proceedings of the International Computer Music
+ * Conference (ICMC), 2007, Copenhagen, Denmark.
+ */
+pub struct SpectralWhitening {
+    whitening: *mut ffi::aubio_spectral_whitening_t,
+    buf_size: usize,
+}
+
+impl Drop for SpectralWhitening {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::del_aubio_spectral_whitening(self.whitening);
+        }
+    }
+}
+
+unsafe impl Send for SpectralWhitening {}
+
+impl SpectralWhitening {
+    /**
+     * Create spectral whitening object
+     *
+     * - `buf_size` Window size of input grains
+     * - `hop_size` Number of samples between two consecutive input grains
+     * - `sample_rate` Sampling rate of the input signal
+     */
+    pub fn new(buf_size: usize, hop_size: usize, sample_rate: u32) -> Result<Self> {
+        let whitening = check_init(|| unsafe {
+            ffi::new_aubio_spectral_whitening(
+                buf_size as ffi::uint_t,
+                hop_size as ffi::uint_t,
+                sample_rate as ffi::uint_t,
+            )
+        })?;
+
+        Ok(Self {
+            whitening,
+            buf_size,
+        })
+    }
+
+    /**
+     * Set relaxation time
+     */
+    pub fn with_relax_time(mut self, relax_time: f32) -> Self {
+        self.set_relax_time(relax_time);
+        self
+    }
+
+    /**
+     * Set floor
+     */
+    pub fn with_floor(mut self, floor: f32) -> Self {
+        self.set_floor(floor);
+        self
+    }
+
+    /**
+     * Get buffer size
+     */
+    pub fn get_buf(&self) -> usize {
+        self.buf_size
+    }
+
+    /**
+     * Whiten spectral frame in place
+     *
+     * - `fftgrain` Spectral frame to whiten (`buf_size` long)
+     */
+    pub fn do_<'io, IO>(&mut self, fftgrain: IO) -> Status
+    where
+        IO: Into<CVecMut<'io>>,
+    {
+        let mut fftgrain = fftgrain.into();
+
+        fftgrain.check_size(self.get_buf())?;
+
+        unsafe {
+            ffi::aubio_spectral_whitening_do(self.whitening, fftgrain.as_mut_ptr());
+        }
+        Ok(())
+    }
+
+    /**
+     * Reset peak estimates
+     */
+    pub fn reset(&mut self) {
+        unsafe {
+            ffi::aubio_spectral_whitening_reset(self.whitening);
+        }
+    }
+
+    /**
+     * Set relaxation time, in seconds
+     *
+     * Typical values are between 20 and 500, defaults to 250.
+     */
+    pub fn set_relax_time(&mut self, relax_time: f32) {
+        unsafe {
+            ffi::aubio_spectral_whitening_set_relax_time(self.whitening, relax_time);
+        }
+    }
+
+    /**
+     * Get relaxation time, in seconds
+     */
+    pub fn get_relax_time(&self) -> f32 {
+        unsafe { ffi::aubio_spectral_whitening_get_relax_time(self.whitening) }
+    }
+
+    /**
+     * Set floor
+     *
+     * Typical values are between 1.e-6 and .2, defaults to 1.e-4.
+     */
+    pub fn set_floor(&mut self, floor: f32) {
+        unsafe {
+            ffi::aubio_spectral_whitening_set_floor(self.whitening, floor);
+        }
+    }
+
+    /**
+     * Get floor
+     */
+    pub fn get_floor(&self) -> f32 {
+        unsafe { ffi::aubio_spectral_whitening_get_floor(self.whitening) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test() {
+        const WIN_S: usize = 512;
+        const HOP_S: usize = WIN_S / 4;
+        const SAMPLERATE: u32 = 44100;
+
+        let mut in_ = [0f32; HOP_S];
+        let mut fftgrain = carr!(WIN_S);
+
+        let mut pv = PVoc::new(WIN_S, HOP_S).unwrap();
+        let mut whitening = SpectralWhitening::new(WIN_S, HOP_S, SAMPLERATE)
+            .unwrap()
+            .with_relax_time(100.0)
+            .with_floor(1.0e-3);
+
+        assert_eq!(whitening.get_buf(), WIN_S);
+        assert!((whitening.get_relax_time() - 100.0).abs() < 1e-3);
+        assert!((whitening.get_floor() - 1.0e-3).abs() < 1e-6);
+
+        for (i, v) in in_.iter_mut().enumerate() {
+            *v = (i as f32 * 0.1).sin();
+        }
+
+        pv.do_(in_.as_ref(), fftgrain.as_mut()).unwrap();
+        whitening.do_(fftgrain.as_mut()).unwrap();
+
+        // whitened magnitudes are normalized by their peak
+        assert!(fftgrain[..WIN_S / 2 + 1].iter().all(|v| *v <= 1.0 + 1e-6));
+
+        whitening.reset();
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{
+    check_init, ffi,
+    vec::{FVec, FVecMut},
+    Error, Result, Status,
+};
+
+/**
+ * DCT (Discrete Cosine Transformation) object
+ *
+ * This object computes forward and backward DCT type 2 with orthonormal scaling.
+ *
+ * Depending on how _aubio_ was compiled, DCT are computed using one of:
+ *
+ * - Ooura
+ * - FFTW3
+ * - vDSP
+ * - Intel IPP
+ *
+ * When the size is not a power of 2, a plain (slow) implementation is used.
+ */
+pub struct Dct {
+    dct: *mut ffi::aubio_dct_t,
+    size: usize,
+}
+
+impl Drop for Dct {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::del_aubio_dct(self.dct);
+        }
+    }
+}
+
+unsafe impl Send for Dct {}
+
+impl Dct {
+    /**
+     * Create new DCT computation object
+     */
+    pub fn new(size: usize) -> Result<Self> {
+        let dct = check_init(|| unsafe { ffi::new_aubio_dct(size as ffi::uint_t) })?;
+
+        Ok(Self { dct, size })
+    }
+
+    /**
+     * Get size of transform
+     */
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    /**
+     * Compute forward DCT
+     *
+     * Both `input` and `output` should be `size` long.
+     */
+    pub fn do_<'i, 'o, I, O>(&mut self, input: I, output: O) -> Status
+    where
+        I: Into<FVec<'i>>,
+        O: Into<FVecMut<'o>>,
+    {
+        let input = input.into();
+        let mut output = output.into();
+
+        self.check_sizes(input.size(), output.size())?;
+
+        unsafe {
+            ffi::aubio_dct_do(self.dct, input.as_ptr(), output.as_mut_ptr());
+        }
+        Ok(())
+    }
+
+    /**
+     * Compute forward DCT
+     */
+    pub fn do_result<'i, I>(&mut self, input: I) -> Result<Vec<f32>>
+    where
+        I: Into<FVec<'i>>,
+    {
+        let mut output = vec![0f32; self.size];
+        self.do_(input, output.as_mut_slice())?;
+        Ok(output)
+    }
+
+    /**
+     * Compute backward (inverse) DCT
+     *
+     * Both `input` and `output` should be `size` long.
+     */
+    pub fn rdo<'i, 'o, I, O>(&mut self, input: I, output: O) -> Status
+    where
+        I: Into<FVec<'i>>,
+        O: Into<FVecMut<'o>>,
+    {
+        let input = input.into();
+        let mut output = output.into();
+
+        self.check_sizes(input.size(), output.size())?;
+
+        unsafe {
+            ffi::aubio_dct_rdo(self.dct, input.as_ptr(), output.as_mut_ptr());
+        }
+        Ok(())
+    }
+
+    fn check_sizes(&self, input: usize, output: usize) -> Status {
+        if input != self.size {
+            Err(Error::mismatch_size(self.size, input))
+        } else if output != self.size {
+            Err(Error::mismatch_size(self.size, output))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test() {
+        const SIZE: usize = 16;
+
+        let mut in_ = [0f32; SIZE];
+        let mut out = [0f32; SIZE];
+        let mut dct = Dct::new(SIZE).unwrap();
+
+        assert_eq!(dct.get_size(), SIZE);
+
+        for (i, v) in in_.iter_mut().enumerate() {
+            *v = (i % 4) as f32;
+        }
+
+        let coeffs = dct.do_result(in_.as_ref()).unwrap();
+        assert_eq!(coeffs.len(), SIZE);
+
+        dct.rdo(coeffs.as_slice(), out.as_mut()).unwrap();
+
+        for (a, b) in in_.iter().zip(out.iter()) {
+            assert!((a - b).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_plain() {
+        const SIZE: usize = 13;
+
+        let in_ = [1f32; SIZE];
+        let mut dct = Dct::new(SIZE).unwrap();
+
+        let coeffs = dct.do_result(in_.as_ref()).unwrap();
+
+        // constant signal only has a DC component
+        assert!((coeffs[0] - (SIZE as f32).sqrt()).abs() < 1e-4);
+        assert!(coeffs[1..].iter().all(|c| c.abs() < 1e-4));
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        assert!(Dct::new(0).is_err());
+
+        let mut dct = Dct::new(8).unwrap();
+        let in_ = [0f32; 8];
+        let mut out = [0f32; 4];
+
+        assert_eq!(
+            dct.do_(in_.as_ref(), out.as_mut()),
+            Err(Error::MismatchSize {
+                expected: 8,
+                actual: 4
+            })
+        );
+        assert_eq!(
+            dct.rdo([0f32; 4].as_ref(), [0f32; 8].as_mut()),
+            Err(Error::MismatchSize {
+                expected: 8,
+                actual: 4
+            })
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    check_init, ffi,
+    check_fft_size, check_init, ffi,
     vec::{CVec, CVecMut, CVecNormMut, CVecPhasMut, FVec, FVecMut},
     Result, Status,
 };
@@ -28,14 +28,19 @@ impl Drop for FFT {
     }
 }
 
+// The FFTW planner is guarded by a global mutex inside aubio, other backends keep no shared state
+unsafe impl Send for FFT {}
+
 impl FFT {
     /**
      * Create new FFT computation object
+     *
+     * The `win_size` should be a power of two, unless _aubio_ uses __FFTW3__ (see _with-fftw3_ feature).
      */
     pub fn new(win_size: usize) -> Result<Self> {
-        let fft = unsafe { ffi::new_aubio_fft(win_size as ffi::uint_t) };
+        check_fft_size("win_size", win_size)?;
 
-        check_init(fft)?;
+        let fft = check_init(|| unsafe { ffi::new_aubio_fft(win_size as ffi::uint_t) })?;
 
         Ok(Self { fft, win_size })
     }
@@ -285,4 +290,39 @@ mod test {
 
         println!("out: {:?}", out.as_ref());
     }
+
+    #[test]
+    fn test_bufs() {
+        const WIN: usize = 512; // window size
+
+        let mut in_ = vec::FVecBuf::new(WIN); // input buffer
+        let mut fftgrain = vec::CVecBuf::new(WIN); // fft norm and phase
+        let mut out = vec::FVecBuf::new(WIN); // output buffer
+        let mut fft = FFT::new(WIN).unwrap();
+
+        in_[0] = 1.0;
+        in_[1] = 2.0;
+
+        fft.do_(&in_, &mut fftgrain).unwrap();
+        fft.rdo(fftgrain.as_cvec(), out.as_vec_mut()).unwrap();
+
+        assert!((out[0] - 1.0).abs() < 1e-5);
+        assert!((out[1] - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        let invalid = |value: &str| {
+            Some(Error::InvalidArg {
+                name: "win_size",
+                value: value.into(),
+            })
+        };
+
+        assert_eq!(FFT::new(0).err(), invalid("0"));
+        assert_eq!(FFT::new(1).err(), invalid("1"));
+        if !cfg!(feature = "with-fftw3") {
+            assert_eq!(FFT::new(500).err(), invalid("500"));
+        }
+    }
 }
@@ -0,0 +1,392 @@
+use crate::{
+    check_init, ffi,
+    vec::{FVec, FVecMut, LVec, LVecMut},
+    Error, Result, Status,
+};
+
+/**
+ * Digital filter
+ *
+ * This object stores a digital filter of order `n`.
+ * It contains the following data:
+ *
+ * - `n` feedback coefficients `a_i`
+ * - `n` feedforward coefficients `b_i`
+ * - `n` input values `x_i`
+ * - `n` output values `y_i`
+ *
+ * The filter is applied using the direct form I:
+ *
+ * y[n] = b_0 x[n] + ... + b_{order} x[n-order] - a_1 y[n-1] - ... - a_{order} y[n-order]
+ *
+ * The function `Filter::do_` can be used to compute the output of the filter in place,
+ * while `Filter::do_outplace` stores it in a separate vector.
+ *
+ * The function `Filter::do_filtfilt` runs the filter forward and backward,
+ * which gives a zero-phase response.
+ */
+pub struct Filter {
+    filter: *mut ffi::aubio_filter_t,
+}
+
+impl Drop for Filter {
+    fn drop(&mut self) {
+        unsafe { ffi::del_aubio_filter(self.filter) }
+    }
+}
+
+unsafe impl Send for Filter {}
+
+impl Filter {
+    /**
+     * Create new filter object
+     *
+     * - `order` Order of the filter (number of coefficients)
+     *
+     * The coefficients are initialized to make the filter pass the signal unchanged.
+     */
+    pub fn new(order: usize) -> Result<Self> {
+        let filter = check_init(|| unsafe { ffi::new_aubio_filter(order as ffi::uint_t) })?;
+
+        Ok(Self { filter })
+    }
+
+    /**
+     * Create biquad filter with `b0`, `b1`, `b2`, `a1`, `a2` coefficients
+     *
+     * - `b0`, `b1`, `b2` Forward filter coefficients
+     * - `a1`, `a2` Feedback filter coefficients
+     */
+    pub fn new_biquad(b0: f64, b1: f64, b2: f64, a1: f64, a2: f64) -> Result<Self> {
+        let filter = check_init(|| unsafe { ffi::new_aubio_filter_biquad(b0, b1, b2, a1, a2) })?;
+
+        Ok(Self { filter })
+    }
+
+    /**
+     * Create new A-design filter
+     *
+     * - `sample_rate` Sampling frequency of the signal to filter
+     *
+     * The sampling rate should be one of 8000, 11025, 16000, 22050, 24000, 32000, 44100, 48000, 88200, 96000, and 192000 Hz.
+     */
+    pub fn new_a_weighting(sample_rate: u32) -> Result<Self> {
+        let filter = check_init(|| unsafe {
+            ffi::new_aubio_filter_a_weighting(sample_rate as ffi::uint_t)
+        })?;
+
+        Ok(Self { filter })
+    }
+
+    /**
+     * Create new C-design filter
+     *
+     * - `sample_rate` Sampling frequency of the signal to filter
+     *
+     * The sampling rate should be one of 8000, 11025, 16000, 22050, 24000, 32000, 44100, 48000, 88200, 96000, and 192000 Hz.
+     */
+    pub fn new_c_weighting(sample_rate: u32) -> Result<Self> {
+        let filter = check_init(|| unsafe {
+            ffi::new_aubio_filter_c_weighting(sample_rate as ffi::uint_t)
+        })?;
+
+        Ok(Self { filter })
+    }
+
+    /**
+     * Filter input vector (in-place)
+     */
+    pub fn do_<'io, IO>(&mut self, data: IO)
+    where
+        IO: Into<FVecMut<'io>>,
+    {
+        let mut data = data.into();
+
+        unsafe { ffi::aubio_filter_do(self.filter, data.as_mut_ptr()) }
+    }
+
+    /**
+     * Filter input vector (out-of-place)
+     *
+     * - `input` Input vector to filter
+     * - `output` Output vector to store filtered input (of the same size)
+     */
+    pub fn do_outplace<'i, 'o, I, O>(&mut self, input: I, output: O) -> Status
+    where
+        I: Into<FVec<'i>>,
+        O: Into<FVecMut<'o>>,
+    {
+        let input = input.into();
+        let mut output = output.into();
+
+        if input.size() != output.size() {
+            return Err(Error::mismatch_size(input.size(), output.size()));
+        }
+
+        unsafe { ffi::aubio_filter_do_outplace(self.filter, input.as_ptr(), output.as_mut_ptr()) }
+        Ok(())
+    }
+
+    /**
+     * Filter input vector forward and backward (in-place)
+     *
+     * - `data` Vector to filter
+     * - `tmp` Memory space to use for computation (at least the size of `data`)
+     */
+    pub fn do_filtfilt<'io, 't, IO, T>(&mut self, data: IO, tmp: T) -> Status
+    where
+        IO: Into<FVecMut<'io>>,
+        T: Into<FVecMut<'t>>,
+    {
+        let mut data = data.into();
+        let mut tmp = tmp.into();
+
+        if tmp.size() < data.size() {
+            return Err(Error::mismatch_size(data.size(), tmp.size()));
+        }
+
+        unsafe { ffi::aubio_filter_do_filtfilt(self.filter, data.as_mut_ptr(), tmp.as_mut_ptr()) }
+        Ok(())
+    }
+
+    /**
+     * Set coefficients of a biquad filter
+     *
+     * The filter should be of order 3.
+     */
+    pub fn set_biquad(&mut self, b0: f64, b1: f64, b2: f64, a1: f64, a2: f64) -> Status {
+        if 0 == unsafe { ffi::aubio_filter_set_biquad(self.filter, b0, b1, b2, a1, a2) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("order", self.get_order()))
+        }
+    }
+
+    /**
+     * Set feedback and feedforward coefficients of an A-weighting filter
+     *
+     * The filter should be of order 7.
+     */
+    pub fn set_a_weighting(&mut self, sample_rate: u32) -> Status {
+        if 0 == unsafe {
+            ffi::aubio_filter_set_a_weighting(self.filter, sample_rate as ffi::uint_t)
+        } {
+            Ok(())
+        } else if self.get_order() != 7 {
+            Err(Error::invalid_arg("order", self.get_order()))
+        } else {
+            Err(Error::invalid_arg("sample_rate", sample_rate))
+        }
+    }
+
+    /**
+     * Set feedback and feedforward coefficients of a C-weighting filter
+     *
+     * The filter should be of order 5.
+     */
+    pub fn set_c_weighting(&mut self, sample_rate: u32) -> Status {
+        if 0 == unsafe {
+            ffi::aubio_filter_set_c_weighting(self.filter, sample_rate as ffi::uint_t)
+        } {
+            Ok(())
+        } else if self.get_order() != 5 {
+            Err(Error::invalid_arg("order", self.get_order()))
+        } else {
+            Err(Error::invalid_arg("sample_rate", sample_rate))
+        }
+    }
+
+    /**
+     * Get feedback coefficients a_0 .. a_order
+     */
+    pub fn get_feedback(&self) -> &[f64] {
+        unsafe { LVec::slice_from_raw_ptr(ffi::aubio_filter_get_feedback(self.filter)) }
+    }
+
+    /**
+     * Get mutable feedback coefficients a_0 .. a_order
+     */
+    pub fn get_feedback_mut(&mut self) -> &mut [f64] {
+        unsafe { LVecMut::slice_from_raw_ptr(ffi::aubio_filter_get_feedback(self.filter)) }
+    }
+
+    /**
+     * Set feedback coefficients a_0 .. a_order
+     *
+     * The number of coefficients should be equal to the order of the filter.
+     */
+    pub fn set_feedback<'i, I>(&mut self, coeffs: I) -> Status
+    where
+        I: Into<LVec<'i>>,
+    {
+        let coeffs = coeffs.into();
+
+        if coeffs.size() != self.get_order() {
+            return Err(Error::mismatch_size(self.get_order(), coeffs.size()));
+        }
+
+        self.get_feedback_mut().copy_from_slice(coeffs.as_slice());
+        Ok(())
+    }
+
+    /**
+     * Get feedforward coefficients b_0 .. b_order
+     */
+    pub fn get_feedforward(&self) -> &[f64] {
+        unsafe { LVec::slice_from_raw_ptr(ffi::aubio_filter_get_feedforward(self.filter)) }
+    }
+
+    /**
+     * Get mutable feedforward coefficients b_0 .. b_order
+     */
+    pub fn get_feedforward_mut(&mut self) -> &mut [f64] {
+        unsafe { LVecMut::slice_from_raw_ptr(ffi::aubio_filter_get_feedforward(self.filter)) }
+    }
+
+    /**
+     * Set feedforward coefficients b_0 .. b_order
+     *
+     * The number of coefficients should be equal to the order of the filter.
+     */
+    pub fn set_feedforward<'i, I>(&mut self, coeffs: I) -> Status
+    where
+        I: Into<LVec<'i>>,
+    {
+        let coeffs = coeffs.into();
+
+        if coeffs.size() != self.get_order() {
+            return Err(Error::mismatch_size(self.get_order(), coeffs.size()));
+        }
+
+        self.get_feedforward_mut()
+            .copy_from_slice(coeffs.as_slice());
+        Ok(())
+    }
+
+    /**
+     * Get order of the filter
+     */
+    pub fn get_order(&self) -> usize {
+        (unsafe { ffi::aubio_filter_get_order(self.filter) }) as usize
+    }
+
+    /**
+     * Get sampling rate of the filter, in Hz
+     */
+    pub fn get_samplerate(&self) -> u32 {
+        (unsafe { ffi::aubio_filter_get_samplerate(self.filter) }) as u32
+    }
+
+    /**
+     * Set sampling rate of the filter, in Hz
+     *
+     * This does not recompute the coefficients.
+     */
+    pub fn set_samplerate(&mut self, sample_rate: u32) {
+        unsafe {
+            ffi::aubio_filter_set_samplerate(self.filter, sample_rate as ffi::uint_t);
+        }
+    }
+
+    /**
+     * Reset filter memory
+     */
+    pub fn reset(&mut self) {
+        unsafe {
+            ffi::aubio_filter_do_reset(self.filter);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test() {
+        let mut filter = Filter::new_biquad(0.5, 0.0, 0.0, 0.0, 0.0).unwrap();
+
+        assert_eq!(filter.get_order(), 3);
+        assert_eq!(filter.get_feedforward(), &[0.5, 0.0, 0.0]);
+        assert_eq!(filter.get_feedback(), &[1.0, 0.0, 0.0]);
+
+        let mut data = [1f32, 2.0, 3.0, 4.0];
+        filter.do_(data.as_mut());
+        assert_eq!(data, [0.5, 1.0, 1.5, 2.0]);
+
+        let mut out = [0f32; 4];
+        filter.do_outplace(data.as_ref(), out.as_mut()).unwrap();
+        assert_eq!(out, [0.25, 0.5, 0.75, 1.0]);
+
+        filter.get_feedforward_mut()[0] = 1.0;
+        let mut tmp = [0f32; 4];
+        filter.do_filtfilt(data.as_mut(), tmp.as_mut()).unwrap();
+        assert_eq!(data, [0.5, 1.0, 1.5, 2.0]);
+
+        filter.set_biquad(1.0, 0.0, 0.0, 0.0, 0.0).unwrap();
+        filter.reset();
+
+        filter.set_feedforward([0.25, 0.25, 0.0].as_ref()).unwrap();
+        filter.set_feedback([1.0, 0.0, 0.0].as_ref()).unwrap();
+        assert_eq!(filter.get_feedforward(), &[0.25, 0.25, 0.0]);
+
+        let mut data = [1f32, 1.0, 1.0, 1.0];
+        filter.do_(data.as_mut());
+        assert_eq!(data, [0.25, 0.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_weighting() {
+        let mut filter = Filter::new_a_weighting(44100).unwrap();
+        assert_eq!(filter.get_order(), 7);
+        assert_eq!(filter.get_samplerate(), 44100);
+
+        filter.set_a_weighting(48000).unwrap();
+        assert_eq!(filter.get_samplerate(), 48000);
+
+        let mut data = [1f32; 64];
+        filter.do_(data.as_mut());
+
+        let filter = Filter::new_c_weighting(44100).unwrap();
+        assert_eq!(filter.get_order(), 5);
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        assert!(Filter::new_a_weighting(1234).is_err());
+        assert!(Filter::new_c_weighting(1234).is_err());
+
+        let mut filter = Filter::new(3).unwrap();
+        assert_eq!(
+            filter.set_a_weighting(44100),
+            Err(Error::InvalidArg {
+                name: "order",
+                value: "3".into()
+            })
+        );
+
+        let mut data = [0f32; 4];
+        let mut out = [0f32; 2];
+        assert_eq!(
+            filter.do_outplace(data.as_ref(), out.as_mut()),
+            Err(Error::MismatchSize {
+                expected: 4,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            filter.do_filtfilt(data.as_mut(), out.as_mut()),
+            Err(Error::MismatchSize {
+                expected: 4,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            filter.set_feedback([1.0, 0.0].as_ref()),
+            Err(Error::MismatchSize {
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+}
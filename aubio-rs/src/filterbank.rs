@@ -1,7 +1,8 @@
 use crate::{
-    check_init,
+    check_arg, check_init,
     ffi,
-    vec::{FVecMut, CVec, FMat, FMatVecs},
+    vec::{FVec, FVecMut, CVec, FMat, FMatVecs},
+    Error,
     Result,
     Status,
 };
@@ -13,7 +14,6 @@ use crate::{
  */
 pub struct FilterBank {
     filterbank: *mut ffi::aubio_filterbank_t,
-    #[cfg(feature = "check-size")]
     n_filters: usize,
     #[cfg(feature = "check-size")]
     win_s: usize,
@@ -25,6 +25,8 @@ impl Drop for FilterBank {
     }
 }
 
+unsafe impl Send for FilterBank {}
+
 impl FilterBank {
     /**
      * Create filterbank object.
@@ -35,11 +37,12 @@ impl FilterBank {
      * Allocates an empty matrix of length win_s / 2 + 1 and height n_filters
      */
     pub fn new(n_filters: usize, win_s: usize) -> Result<Self> {
-        let filterbank = unsafe {
-            ffi::new_aubio_filterbank(n_filters as ffi::uint_t, win_s as ffi::uint_t)
-        };
+        check_arg("n_filters", n_filters, n_filters >= 1)?;
+        check_arg("win_s", win_s, win_s >= 1)?;
 
-        check_init(filterbank)?;
+        let filterbank = check_init(|| unsafe {
+            ffi::new_aubio_filterbank(n_filters as ffi::uint_t, win_s as ffi::uint_t)
+        })?;
 
         #[cfg(feature = "check-size")]
         {
@@ -47,10 +50,70 @@ impl FilterBank {
         }
         #[cfg(not(feature = "check-size"))]
         {
-            Ok(Self { filterbank })
+            Ok(Self { filterbank, n_filters })
         }
     }
 
+    /**
+     * Set norm parameter
+     */
+    pub fn with_norm(mut self, norm: bool) -> Self {
+        self.set_norm(norm);
+        self
+    }
+
+    /**
+     * Set power parameter
+     */
+    pub fn with_power(mut self, power: f32) -> Self {
+        self.set_power(power);
+        self
+    }
+
+    /**
+     * Filterbank initialization with triangular and overlapping bands
+     *
+     * - `freqs` Arbitrary array of boundary frequencies, in Hz (`n_filters + 2` long)
+     * - `sample_rate` Audio sampling rate, in Hz
+     */
+    pub fn with_triangle_bands<'f, F>(mut self, freqs: F, sample_rate: u32) -> Result<Self>
+    where
+        F: Into<FVec<'f>>,
+    {
+        self.set_triangle_bands(freqs, sample_rate).map(|_| self)
+    }
+
+    /**
+     * Mel filterbank initialization
+     *
+     * - `sample_rate` Audio sampling rate, in Hz
+     * - `fmin` Start frequency, in Hz
+     * - `fmax` End frequency, in Hz
+     */
+    pub fn with_mel_coeffs(mut self, sample_rate: u32, fmin: f32, fmax: f32) -> Result<Self> {
+        self.set_mel_coeffs(sample_rate, fmin, fmax).map(|_| self)
+    }
+
+    /**
+     * Mel filterbank initialization (HTK mel scale)
+     *
+     * - `sample_rate` Audio sampling rate, in Hz
+     * - `fmin` Start frequency, in Hz
+     * - `fmax` End frequency, in Hz
+     */
+    pub fn with_mel_coeffs_htk(mut self, sample_rate: u32, fmin: f32, fmax: f32) -> Result<Self> {
+        self.set_mel_coeffs_htk(sample_rate, fmin, fmax).map(|_| self)
+    }
+
+    /**
+     * Mel filterbank initialization (Auditory Toolbox's parameters)
+     *
+     * - `sample_rate` Audio sampling rate, in Hz
+     */
+    pub fn with_mel_coeffs_slaney(mut self, sample_rate: u32) -> Result<Self> {
+        self.set_mel_coeffs_slaney(sample_rate).map(|_| self)
+    }
+
     pub fn set_coeffs(&mut self, filters: FMat<FMatVecs>) {
         #[cfg(feature = "check-size")]
         {
@@ -81,6 +144,147 @@ impl FilterBank {
         unsafe { ffi::aubio_filterbank_do (self.filterbank, input.as_ptr(), output.as_mut_ptr()) };
         Ok(())
     }
+
+    /**
+     * Set norm parameter
+     *
+     * If set to `false`, the filters will not be normalized.
+     * If set to `true`, each filter will be normalized to one. Defaults to `true`.
+     *
+     * This function should be called *before* setting the filters with one of
+     * `set_triangle_bands()`, `set_mel_coeffs()`, `set_mel_coeffs_htk()`
+     * or `set_mel_coeffs_slaney()`.
+     */
+    pub fn set_norm(&mut self, norm: bool) {
+        unsafe {
+            ffi::aubio_filterbank_set_norm(self.filterbank, if norm { 1.0 } else { 0.0 });
+        }
+    }
+
+    /**
+     * Get norm parameter
+     */
+    pub fn get_norm(&self) -> bool {
+        0.0 != unsafe { ffi::aubio_filterbank_get_norm(self.filterbank) }
+    }
+
+    /**
+     * Set power parameter
+     *
+     * The norm of the input spectrum is raised to this power before computing filterbank.
+     * Defaults to `1`.
+     */
+    pub fn set_power(&mut self, power: f32) {
+        unsafe {
+            ffi::aubio_filterbank_set_power(self.filterbank, power);
+        }
+    }
+
+    /**
+     * Get power parameter
+     */
+    pub fn get_power(&self) -> f32 {
+        unsafe { ffi::aubio_filterbank_get_power(self.filterbank) }
+    }
+
+    /**
+     * Filterbank initialization with triangular and overlapping bands
+     *
+     * - `freqs` Arbitrary array of boundary frequencies, in Hz (`n_filters + 2` long)
+     * - `sample_rate` Audio sampling rate, in Hz
+     *
+     * This function computes the coefficients of the filterbank based on the boundaries
+     * found in `freqs` and using triangular overlapping bands.
+     * It can be used to build Bark, octave or any other custom band energies.
+     */
+    pub fn set_triangle_bands<'f, F>(&mut self, freqs: F, sample_rate: u32) -> Status
+    where
+        F: Into<FVec<'f>>,
+    {
+        let freqs = freqs.into();
+
+        // aubio reads `n_filters + 2` boundaries regardless of the vector size
+        if freqs.size() < self.n_filters + 2 {
+            return Err(Error::mismatch_size(self.n_filters + 2, freqs.size()));
+        }
+
+        if 0 == unsafe {
+            ffi::aubio_filterbank_set_triangle_bands(self.filterbank, freqs.as_ptr(), sample_rate as f32)
+        } {
+            Ok(())
+        } else if sample_rate == 0 {
+            Err(Error::invalid_arg("sample_rate", sample_rate))
+        } else {
+            Err(Error::invalid_arg("freqs", format!("{} frequencies", freqs.size())))
+        }
+    }
+
+    /**
+     * Mel filterbank initialization
+     *
+     * - `sample_rate` Audio sampling rate, in Hz
+     * - `fmin` Start frequency, in Hz
+     * - `fmax` End frequency, in Hz
+     *
+     * The filterbank will be initialized with bands linearly spaced in the mel scale,
+     * from `fmin` to `fmax`.
+     */
+    pub fn set_mel_coeffs(&mut self, sample_rate: u32, fmin: f32, fmax: f32) -> Status {
+        if 0 == unsafe {
+            ffi::aubio_filterbank_set_mel_coeffs(self.filterbank, sample_rate as f32, fmin, fmax)
+        } {
+            Ok(())
+        } else {
+            Err(mel_coeffs_error(sample_rate, fmin, fmax))
+        }
+    }
+
+    /**
+     * Mel filterbank initialization (HTK mel scale)
+     *
+     * - `sample_rate` Audio sampling rate, in Hz
+     * - `fmin` Start frequency, in Hz
+     * - `fmax` End frequency, in Hz
+     *
+     * The filterbank will be initialized with bands linearly spaced in the HTK mel scale,
+     * from `fmin` to `fmax`.
+     */
+    pub fn set_mel_coeffs_htk(&mut self, sample_rate: u32, fmin: f32, fmax: f32) -> Status {
+        if 0 == unsafe {
+            ffi::aubio_filterbank_set_mel_coeffs_htk(self.filterbank, sample_rate as f32, fmin, fmax)
+        } {
+            Ok(())
+        } else {
+            Err(mel_coeffs_error(sample_rate, fmin, fmax))
+        }
+    }
+
+    /**
+     * Mel filterbank initialization (Auditory Toolbox's parameters)
+     *
+     * - `sample_rate` Audio sampling rate, in Hz
+     *
+     * The filter coefficients are built to match exactly Malcolm Slaney's Auditory Toolbox implementation. The number of filters should be 40.
+     */
+    pub fn set_mel_coeffs_slaney(&mut self, sample_rate: u32) -> Status {
+        if 0 == unsafe {
+            ffi::aubio_filterbank_set_mel_coeffs_slaney(self.filterbank, sample_rate as f32)
+        } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("sample_rate", sample_rate))
+        }
+    }
+}
+
+fn mel_coeffs_error(sample_rate: u32, fmin: f32, fmax: f32) -> Error {
+    if sample_rate == 0 {
+        Error::invalid_arg("sample_rate", sample_rate)
+    } else if fmin < 0.0 {
+        Error::invalid_arg("fmin", fmin)
+    } else {
+        Error::invalid_arg("fmax", fmax)
+    }
 }
 
 #[cfg(test)]
@@ -142,6 +346,53 @@ mod test {
         assert_eq!(vec![6.0, 12.0], output);
     }
 
+    #[test]
+    fn test_triangle_bands() {
+        let freqs = [0.0, 1000.0, 2000.0, 4000.0, 8000.0];
+        let mut filter_bank = FilterBank::new(3, 512)
+            .unwrap()
+            .with_power(2.0)
+            .with_triangle_bands(freqs.as_ref(), 44100)
+            .unwrap();
+
+        assert!(filter_bank.get_norm());
+        assert_eq!(filter_bank.get_power(), 2.0);
+
+        let coeffs = filter_bank.get_coeffs();
+        assert_eq!(coeffs.height(), 3);
+        assert!(coeffs.get_vec().iter().all(|band| band.iter().any(|c| *c > 0.0)));
+    }
+
+    #[test]
+    fn test_mel_coeffs() {
+        let mut filter_bank = FilterBank::new(40, 512)
+            .unwrap()
+            .with_norm(false)
+            .with_mel_coeffs(44100, 0.0, 8000.0)
+            .unwrap();
+
+        assert!(!filter_bank.get_norm());
+
+        filter_bank.set_mel_coeffs_htk(44100, 0.0, 8000.0).unwrap();
+        filter_bank.set_mel_coeffs_slaney(44100).unwrap();
+
+        let input = [1f32; 2 * (512 / 2 + 1)];
+        let mut output = [0f32; 40];
+        filter_bank.do_(input.as_ref(), output.as_mut()).unwrap();
+        assert!(output.iter().any(|e| *e > 0.0));
+    }
+
+    #[test]
+    fn test_wrong_mel_coeffs() {
+        let mut filter_bank = FilterBank::new(40, 512).unwrap();
+
+        assert_eq!(filter_bank.set_mel_coeffs(44100, -1.0, 8000.0), Err(Error::InvalidArg { name: "fmin", value: "-1".into() }));
+        assert!(FilterBank::new(40, 512).unwrap().with_mel_coeffs_htk(0, 0.0, 8000.0).is_err());
+
+        let freqs = [0.0, 100.0, 200.0];
+        assert_eq!(filter_bank.set_triangle_bands(&freqs[..], 44100), Err(Error::MismatchSize { expected: 42, actual: 3 }));
+    }
+
     #[cfg(feature="check-size")]
     #[should_panic]
     #[test]
@@ -163,4 +414,17 @@ mod test {
 
         filter_bank.do_(input.as_slice().as_ref(), output.as_mut_slice().as_mut()).unwrap();
     }
+
+    #[test]
+    fn test_wrong_params() {
+        let invalid = |name, value: &str| {
+            Some(Error::InvalidArg {
+                name,
+                value: value.into(),
+            })
+        };
+
+        assert_eq!(FilterBank::new(0, 512).err(), invalid("n_filters", "0"));
+        assert_eq!(FilterBank::new(40, 0).err(), invalid("win_s", "0"));
+    }
 }
@@ -0,0 +1,549 @@
+use crate::{
+    Error, Note, NoteEvent, NoteTracker, Notes, Onset, OnsetEvent, PVoc, Pitch, Result, Tempo,
+};
+
+use std::io::{ErrorKind, Read};
+
+/**
+ * Block of samples of hop size
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hop {
+    /**
+     * Index of hop in stream
+     */
+    pub index: usize,
+
+    /**
+     * Position of first sample of hop, in samples
+     */
+    pub position: usize,
+
+    /**
+     * Position of first sample of hop, in seconds
+     */
+    pub time: f32,
+
+    /**
+     * Number of samples which has been read from stream
+     *
+     * It is less than hop size for the last hop only.
+     */
+    pub length: usize,
+
+    /**
+     * Samples of hop padded by zeros
+     */
+    pub samples: Vec<f32>,
+}
+
+/**
+ * Value which is bound to the hop it has been computed from
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stamped<T> {
+    /**
+     * Index of hop in stream
+     */
+    pub index: usize,
+
+    /**
+     * Position of first sample of hop, in samples
+     */
+    pub position: usize,
+
+    /**
+     * Position of first sample of hop, in seconds
+     */
+    pub time: f32,
+
+    /**
+     * Computed value
+     */
+    pub value: T,
+}
+
+/**
+ * Hop-by-hop processing
+ *
+ * This trait is implemented for analyzers which accepts input signal in blocks of `hop_size`.
+ */
+pub trait HopProcessor {
+    /**
+     * The result of processing single hop
+     */
+    type Output;
+
+    /**
+     * Get hop size
+     */
+    fn get_hop(&self) -> usize;
+
+    /**
+     * Process single hop of input signal
+     */
+    fn process_hop(&mut self, input: &[f32]) -> Result<Self::Output>;
+}
+
+impl<T: HopProcessor> HopProcessor for &mut T {
+    type Output = T::Output;
+
+    fn get_hop(&self) -> usize {
+        (**self).get_hop()
+    }
+
+    fn process_hop(&mut self, input: &[f32]) -> Result<Self::Output> {
+        (**self).process_hop(input)
+    }
+}
+
+impl HopProcessor for Onset {
+    type Output = Option<OnsetEvent>;
+
+    fn get_hop(&self) -> usize {
+        self.get_hop()
+    }
+
+    fn process_hop(&mut self, input: &[f32]) -> Result<Self::Output> {
+        self.do_result(input)
+    }
+}
+
+impl HopProcessor for Pitch {
+    type Output = f32;
+
+    fn get_hop(&self) -> usize {
+        self.get_hop()
+    }
+
+    fn process_hop(&mut self, input: &[f32]) -> Result<Self::Output> {
+        self.do_result(input)
+    }
+}
+
+impl HopProcessor for Tempo {
+    type Output = f32;
+
+    fn get_hop(&self) -> usize {
+        self.get_hop()
+    }
+
+    fn process_hop(&mut self, input: &[f32]) -> Result<Self::Output> {
+        self.do_result(input)
+    }
+}
+
+impl HopProcessor for Notes {
+    type Output = Vec<Note>;
+
+    fn get_hop(&self) -> usize {
+        self.get_hop()
+    }
+
+    fn process_hop(&mut self, input: &[f32]) -> Result<Self::Output> {
+        self.do_result(input)
+    }
+}
+
+impl HopProcessor for NoteTracker {
+    type Output = Option<NoteEvent>;
+
+    fn get_hop(&self) -> usize {
+        self.get_hop()
+    }
+
+    fn process_hop(&mut self, input: &[f32]) -> Result<Self::Output> {
+        self.do_result(input)
+    }
+}
+
+impl HopProcessor for PVoc {
+    /**
+     * Spectral frame (norm followed by phas, `win_size / 2 + 1` long each)
+     */
+    type Output = Vec<f32>;
+
+    fn get_hop(&self) -> usize {
+        self.get_hop()
+    }
+
+    fn process_hop(&mut self, input: &[f32]) -> Result<Self::Output> {
+        let mut fftgrain = vec![0f32; self.get_win() + 2];
+        self.do_(input, fftgrain.as_mut_slice())?;
+        Ok(fftgrain)
+    }
+}
+
+/**
+ * Hops splitter
+ *
+ * Splits stream of samples into hops of fixed size.
+ * The last incomplete hop will be padded by zeros.
+ *
+ * ```
+ * # extern crate aubio_lib;
+ * use aubio_rs::{Hops, Onset, OnsetMode};
+ *
+ * let samples = (0..4096).map(|i| if i % 1024 < 16 { 1.0 } else { 0.0 });
+ * let onset = Onset::new(OnsetMode::Energy, 1024, 256, 44100).unwrap();
+ *
+ * for onset in Hops::new(samples, 256, 44100).process_with(onset).unwrap() {
+ *     if let Some(onset) = onset.unwrap().value {
+ *         println!("onset at {}s", onset.time);
+ *     }
+ * }
+ * ```
+ */
+pub struct Hops<I> {
+    samples: I,
+    hop_size: usize,
+    sample_rate: u32,
+    index: usize,
+    done: bool,
+}
+
+impl<I> Hops<I>
+where
+    I: Iterator<Item = f32>,
+{
+    /**
+     * Create hops splitter
+     *
+     * - `samples` Samples of input signal
+     * - `hop_size` Number of samples in each hop
+     * - `sample_rate` Sampling rate of input signal
+     */
+    pub fn new<S>(samples: S, hop_size: usize, sample_rate: u32) -> Self
+    where
+        S: IntoIterator<IntoIter = I, Item = f32>,
+    {
+        Self {
+            samples: samples.into_iter(),
+            hop_size,
+            sample_rate,
+            index: 0,
+            done: false,
+        }
+    }
+
+    /**
+     * Get hop size
+     */
+    pub fn get_hop(&self) -> usize {
+        self.hop_size
+    }
+
+    /**
+     * Process each hop using analyzer
+     *
+     * Returns an error when hop size of analyzer differs.
+     */
+    pub fn process_with<A>(self, processor: A) -> Result<Processed<I, A>>
+    where
+        A: HopProcessor,
+    {
+        if processor.get_hop() != self.hop_size {
+            return Err(Error::mismatch_size(self.hop_size, processor.get_hop()));
+        }
+
+        Ok(Processed {
+            hops: self,
+            processor,
+        })
+    }
+}
+
+impl<R> Hops<Pcm<R>>
+where
+    R: Read,
+{
+    /**
+     * Create hops splitter which reads raw PCM data
+     *
+     * - `reader` Source of mono PCM data
+     * - `format` Sample format of PCM data
+     * - `hop_size` Number of samples in each hop
+     * - `sample_rate` Sampling rate of input signal
+     */
+    pub fn from_read(reader: R, format: PcmFormat, hop_size: usize, sample_rate: u32) -> Self {
+        Self::new(Pcm::new(reader, format), hop_size, sample_rate)
+    }
+}
+
+impl<I> Iterator for Hops<I>
+where
+    I: Iterator<Item = f32>,
+{
+    type Item = Hop;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.hop_size == 0 {
+            return None;
+        }
+
+        let mut samples = Vec::with_capacity(self.hop_size);
+        samples.extend(self.samples.by_ref().take(self.hop_size));
+
+        let length = samples.len();
+
+        if length < self.hop_size {
+            self.done = true;
+
+            if length == 0 {
+                return None;
+            }
+
+            samples.resize(self.hop_size, 0.0);
+        }
+
+        let index = self.index;
+        let position = index * self.hop_size;
+        self.index += 1;
+
+        Some(Hop {
+            index,
+            position,
+            time: position as f32 / self.sample_rate as f32,
+            length,
+            samples,
+        })
+    }
+}
+
+/**
+ * Iterator over hops processed by analyzer
+ */
+pub struct Processed<I, A> {
+    hops: Hops<I>,
+    processor: A,
+}
+
+impl<I, A> Processed<I, A> {
+    /**
+     * Get the underlying analyzer
+     */
+    pub fn processor(&self) -> &A {
+        &self.processor
+    }
+
+    /**
+     * Unwrap the underlying analyzer
+     */
+    pub fn into_processor(self) -> A {
+        self.processor
+    }
+}
+
+impl<I, A> Iterator for Processed<I, A>
+where
+    I: Iterator<Item = f32>,
+    A: HopProcessor,
+{
+    type Item = Result<Stamped<A::Output>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let hop = self.hops.next()?;
+
+        Some(
+            self.processor
+                .process_hop(&hop.samples)
+                .map(|value| Stamped {
+                    index: hop.index,
+                    position: hop.position,
+                    time: hop.time,
+                    value,
+                }),
+        )
+    }
+}
+
+/**
+ * Raw PCM sample format
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PcmFormat {
+    /**
+     * Signed 16-bit little-endian
+     */
+    S16LE,
+
+    /**
+     * Signed 32-bit little-endian
+     */
+    S32LE,
+
+    /**
+     * Floating point 32-bit little-endian
+     */
+    F32LE,
+}
+
+impl PcmFormat {
+    /**
+     * Get size of single sample, in bytes
+     */
+    pub fn sample_size(&self) -> usize {
+        use self::PcmFormat::*;
+
+        match self {
+            S16LE => 2,
+            S32LE | F32LE => 4,
+        }
+    }
+
+    fn decode(&self, bytes: [u8; 4]) -> f32 {
+        use self::PcmFormat::*;
+
+        match self {
+            S16LE => i16::from_le_bytes([bytes[0], bytes[1]]) as f32 / (1u32 << 15) as f32,
+            S32LE => i32::from_le_bytes(bytes) as f32 / (1u64 << 31) as f32,
+            F32LE => f32::from_le_bytes(bytes),
+        }
+    }
+}
+
+/**
+ * Iterator over samples of raw PCM data
+ *
+ * The iteration stops at the end of data or at first read error.
+ * The error can be obtained using `take_error()`.
+ */
+pub struct Pcm<R> {
+    reader: R,
+    format: PcmFormat,
+    error: Option<std::io::Error>,
+}
+
+impl<R> Pcm<R> {
+    /**
+     * Create PCM data reader
+     */
+    pub fn new(reader: R, format: PcmFormat) -> Self {
+        Self {
+            reader,
+            format,
+            error: None,
+        }
+    }
+
+    /**
+     * Get read error if any
+     */
+    pub fn take_error(&mut self) -> Option<std::io::Error> {
+        self.error.take()
+    }
+}
+
+impl<R> Iterator for Pcm<R>
+where
+    R: Read,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        let mut bytes = [0u8; 4];
+
+        match self
+            .reader
+            .read_exact(&mut bytes[..self.format.sample_size()])
+        {
+            Ok(_) => Some(self.format.decode(bytes)),
+            Err(error) => {
+                if error.kind() != ErrorKind::UnexpectedEof {
+                    self.error = Some(error);
+                }
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test_hops() {
+        let samples = (0..10).map(|i| i as f32);
+        let hops = Hops::new(samples, 4, 4).collect::<Vec<_>>();
+
+        assert_eq!(hops.len(), 3);
+
+        assert_eq!(hops[0].index, 0);
+        assert_eq!(hops[0].position, 0);
+        assert_eq!(hops[0].time, 0.0);
+        assert_eq!(hops[0].length, 4);
+        assert_eq!(hops[0].samples, &[0.0, 1.0, 2.0, 3.0]);
+
+        assert_eq!(hops[1].index, 1);
+        assert_eq!(hops[1].position, 4);
+        assert_eq!(hops[1].time, 1.0);
+
+        assert_eq!(hops[2].index, 2);
+        assert_eq!(hops[2].position, 8);
+        assert_eq!(hops[2].time, 2.0);
+        assert_eq!(hops[2].length, 2);
+        assert_eq!(hops[2].samples, &[8.0, 9.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_hops_exact() {
+        let hops = Hops::new(vec![1.0; 8], 4, 44100).collect::<Vec<_>>();
+
+        assert_eq!(hops.len(), 2);
+        assert_eq!(hops[1].length, 4);
+
+        assert_eq!(Hops::new(Vec::new(), 4, 44100).count(), 0);
+    }
+
+    #[test]
+    fn test_pcm() {
+        let data: &[u8] = &[0x00, 0x40, 0x00, 0xc0, 0xff];
+        let mut pcm = Pcm::new(data, PcmFormat::S16LE);
+
+        assert_eq!(pcm.next(), Some(0.5));
+        assert_eq!(pcm.next(), Some(-0.5));
+        assert_eq!(pcm.next(), None);
+        assert!(pcm.take_error().is_none());
+
+        let data = [0.25f32.to_le_bytes(), (-1.0f32).to_le_bytes()].concat();
+        let hops = Hops::from_read(data.as_slice(), PcmFormat::F32LE, 4, 44100).collect::<Vec<_>>();
+
+        assert_eq!(hops.len(), 1);
+        assert_eq!(hops[0].length, 2);
+        assert_eq!(hops[0].samples, &[0.25, -1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_process_with() {
+        const HOP_S: usize = 256;
+
+        let samples = (0..HOP_S * 8 + 10).map(|i| if i % 512 < 8 { 1.0 } else { 0.0 });
+        let mut pitch = Pitch::new(PitchMode::Yinfft, HOP_S * 4, HOP_S, 44100).unwrap();
+
+        let pitches = Hops::new(samples, HOP_S, 44100)
+            .process_with(&mut pitch)
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(pitches.len(), 9);
+        assert_eq!(pitches[8].index, 8);
+        assert_eq!(pitches[8].position, HOP_S * 8);
+
+        assert_eq!(
+            Hops::new(Vec::new(), HOP_S / 2, 44100)
+                .process_with(pitch)
+                .err(),
+            Some(Error::MismatchSize {
+                expected: HOP_S / 2,
+                actual: HOP_S
+            })
+        );
+    }
+}
@@ -33,6 +33,22 @@
  * The following features can be used to customize configuration:
  *
  * - _generate-bindings_ which runs __bindgen__ to generate bindings (_useful for unsupported archs_)
+ * - _serde_ which implements `Serialize` and `Deserialize` for enums (using the same names as `FromStr`), configurations and results
+ * - _with-fftw3_ which declares that linked _aubio_ uses __FFTW3__, so FFT sizes which are not powers of two are accepted
+ *
+ * ## Thread safety
+ *
+ * Each processing object (`Onset`, `Pitch`, `Tempo`, `FFT`, `Source` and others) exclusively owns
+ * its underlying _aubio_ object, which does not share any mutable state with other objects.
+ * So all of them are `Send`: they can be created on one thread and moved to another one
+ * (audio callback, worker pool and so on).
+ *
+ * They are not `Sync`, because processing requires exclusive access, but you can wrap them
+ * into a `Mutex` when they should be shared.
+ *
+ * The vector views from [`vec`] module (`FVec`, `CVecMut`, `FMat` and others) are not `Send`,
+ * because they only borrow data through raw pointers. Use owned buffers (`FVecBuf`, `CVecBuf`,
+ * `FMatBuf`) to move data between threads.
  */
 
 pub(crate) use aubio_sys as ffi;
@@ -40,36 +56,88 @@ pub(crate) use aubio_sys as ffi;
 #[cfg(test)]
 use aubio_lib as _;
 
+/**
+ * (De)serialize enums using the same names as `AsRef<str>` and `FromStr`
+ */
+#[cfg(feature = "serde")]
+macro_rules! impl_serde_str {
+    ($($type: ty),*) => {
+        $(
+            impl serde::Serialize for $type {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                    serializer.serialize_str(self.as_ref())
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for $type {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                    let name = String::deserialize(deserializer)?;
+                    name.parse().map_err(|_| serde::de::Error::custom(format_args!("unknown {} `{}`", stringify!($type), name)))
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+macro_rules! impl_serde_str {
+    ($($type: ty),*) => {};
+}
+
+mod awhitening;
+mod dct;
 mod fft;
+mod filter;
+mod hops;
 mod log;
 mod mfcc;
+mod midi;
 mod notes;
 mod onset;
+mod parameter;
 mod pitch;
 mod pvoc;
 mod resampler;
+mod sampler;
+mod sink;
+mod smf;
+mod source;
 mod specdesc;
 mod tempo;
+mod tss;
 mod types;
 mod utils;
+mod wavetable;
 mod winfunc;
 mod filterbank;
 
 pub mod vec;
 
+pub use self::awhitening::*;
+pub use self::dct::*;
 pub use self::fft::*;
+pub use self::filter::*;
 pub use self::filterbank::*;
+pub use self::hops::*;
 pub use self::log::*;
 pub use self::mfcc::*;
+pub use self::midi::*;
 pub use self::notes::*;
 pub use self::onset::*;
+pub use self::parameter::*;
 pub use self::pitch::*;
 pub use self::pvoc::*;
 pub use self::resampler::*;
+pub use self::sampler::*;
+pub use self::sink::*;
+pub use self::smf::*;
+pub use self::source::*;
 pub use self::specdesc::*;
 pub use self::tempo::*;
+pub use self::tss::*;
 pub use self::types::*;
 pub use self::utils::*;
+pub use self::wavetable::*;
 pub use self::winfunc::*;
 
 #[macro_export]
@@ -85,3 +153,121 @@ macro_rules! carr {
         [0f32; $len + 2]
     };
 }
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    thread_local! {
+        static ALLOCS: Cell<Option<usize>> = const { Cell::new(None) };
+    }
+
+    struct CountingAlloc;
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCS.try_with(|allocs| allocs.set(allocs.get().map(|count| count + 1)));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAlloc = CountingAlloc;
+
+    /**
+     * Count allocations which current thread does while calling function
+     */
+    pub(crate) fn count_allocs<R>(func: impl FnOnce() -> R) -> (R, usize) {
+        ALLOCS.with(|allocs| allocs.set(Some(0)));
+        let result = func();
+        let count = ALLOCS.with(|allocs| allocs.replace(None)).unwrap_or(0);
+        (result, count)
+    }
+
+    #[test]
+    fn test_count_allocs() {
+        assert_eq!(count_allocs(|| [0u8; 16].len()), (16, 0));
+        assert_eq!(count_allocs(|| vec![0u8; 16].len()), (16, 1));
+    }
+
+    fn assert_send<T: Send>() {}
+
+    #[test]
+    fn test_send() {
+        assert_send::<Onset>();
+        assert_send::<Pitch>();
+        assert_send::<Tempo>();
+        assert_send::<Notes>();
+        assert_send::<PVoc>();
+        assert_send::<FFT>();
+        assert_send::<MFCC>();
+        assert_send::<SpecDesc>();
+        assert_send::<FilterBank>();
+        assert_send::<Resampler>();
+        assert_send::<Source>();
+        assert_send::<Sink>();
+        assert_send::<Filter>();
+        assert_send::<Dct>();
+        assert_send::<Tss>();
+        assert_send::<SpectralWhitening>();
+        assert_send::<Sampler>();
+        assert_send::<Wavetable>();
+        assert_send::<Parameter>();
+        assert_send::<vec::FVecBuf>();
+        assert_send::<vec::CVecBuf>();
+        assert_send::<vec::FMatBuf>();
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use serde_json::{from_str, to_string};
+
+        assert_eq!(to_string(&OnsetMode::SpecFlux).unwrap(), r#""specflux""#);
+        assert_eq!(to_string(&PitchUnit::Hz).unwrap(), r#""hertz""#);
+        assert_eq!(
+            to_string(&ResampleMode::BestQuality).unwrap(),
+            r#""best_quality""#
+        );
+        assert_eq!(to_string(&LogLevel::Warning).unwrap(), r#""WARNING""#);
+
+        assert_eq!(
+            from_str::<PitchMode>(r#""yinfast""#).unwrap(),
+            PitchMode::Yinfast
+        );
+        assert_eq!(
+            from_str::<SpecShape>(r#""rolloff""#).unwrap(),
+            SpecShape::Rolloff
+        );
+        assert_eq!(
+            from_str::<WindowType>(r#""hanningz""#).unwrap(),
+            WindowType::Hanningz
+        );
+        assert_eq!(from_str::<LogLevel>(r#""DEBUG""#).unwrap(), LogLevel::Debug);
+        assert!(from_str::<OnsetMode>(r#""unknown""#).is_err());
+
+        let note = Note {
+            pitch: 60.0,
+            velocity: 127.0,
+        };
+        let json = to_string(&note).unwrap();
+        assert_eq!(json, r#"{"pitch":60.0,"velocity":127.0}"#);
+        assert_eq!(from_str::<Note>(&json).unwrap(), note);
+
+        let config: PitchConfig = from_str(
+            r#"{"method":"yin","buf_size":2048,"hop_size":512,"sample_rate":44100,"tolerance":0.2,"silence":null}"#,
+        )
+        .unwrap();
+        assert_eq!(config.method, PitchMode::Yin);
+        assert_eq!(config.unit, PitchUnit::Hz);
+        assert_eq!(config.tolerance, Some(0.2));
+    }
+}
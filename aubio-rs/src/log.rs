@@ -1,7 +1,9 @@
-use crate::ffi;
+use crate::{ffi, Error, Result};
 use std::{
+    cell::RefCell,
     ffi::{c_void, CStr},
     fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
 };
 
 /**
@@ -69,6 +71,23 @@ impl Display for LogLevel {
     }
 }
 
+impl FromStr for LogLevel {
+    type Err = Error;
+
+    fn from_str(src: &str) -> Result<Self> {
+        Ok(match src {
+            "ERROR" => LogLevel::Error,
+            "INFO" => LogLevel::Info,
+            "MESSAGE" => LogLevel::Message,
+            "DEBUG" => LogLevel::Debug,
+            "WARNING" => LogLevel::Warning,
+            _ => return Err(Error::invalid_arg("LogLevel", src)),
+        })
+    }
+}
+
+impl_serde_str!(LogLevel);
+
 /**
  * Log output handler
  */
@@ -131,6 +150,7 @@ impl Drop for Log {
         unsafe {
             ffi::aubio_log_reset();
         }
+        set_error_handler();
     }
 }
 
@@ -160,6 +180,7 @@ fn with_global_logger(func: impl FnOnce(&mut Option<Log>)) {
 
     ONCE.call_once(|| unsafe {
         LOG = Box::into_raw(Box::new(Arc::new(Mutex::new(None))));
+        set_error_handler();
     });
 
     let log = (unsafe { &*LOG }).clone();
@@ -253,5 +274,63 @@ where
     let level = LogLevel::from_ffi(level).unwrap();
     let message = unsafe { CStr::from_ptr(message).to_str().unwrap() };
 
+    if level == LogLevel::Error {
+        record_error(message);
+    }
+
     logger.log(level, message);
 }
+
+thread_local! {
+    static ERRORS: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+fn record_error(message: &str) {
+    ERRORS.with(|errors| {
+        if let Some(errors) = &mut *errors.borrow_mut() {
+            errors.push_str(message);
+        }
+    });
+}
+
+/**
+ * Call function, collecting error messages which _aubio_ reports meanwhile on current thread
+ */
+pub(crate) fn capture_errors<R>(func: impl FnOnce() -> R) -> (R, Option<String>) {
+    // make sure that error handler is installed
+    with_global_logger(|_| {});
+
+    let outer = ERRORS.with(|errors| errors.replace(Some(String::new())));
+    let result = func();
+    let errors = ERRORS.with(|errors| errors.replace(outer));
+
+    let message = errors
+        .as_ref()
+        .map(|errors| errors.trim())
+        .filter(|errors| !errors.is_empty())
+        .map(String::from);
+
+    (result, message)
+}
+
+/**
+ * Install handler which captures errors when no custom logger is set
+ */
+fn set_error_handler() {
+    unsafe {
+        ffi::aubio_log_set_level_function(
+            ffi::aubio_log_level_AUBIO_LOG_ERR as _,
+            Some(error_handler),
+            std::ptr::null_mut(),
+        );
+    }
+}
+
+extern "C" fn error_handler(_level: ffi::sint_t, message: *const ffi::char_t, _data: *mut c_void) {
+    let message = unsafe { CStr::from_ptr(message) }.to_string_lossy();
+
+    record_error(&message);
+
+    // same as default aubio logger
+    eprint!("{}", message);
+}
@@ -1,9 +1,119 @@
 use crate::{
-    check_init, ffi,
+    check_arg, check_init, check_sample_rate, ffi,
     vec::{CVec, FVecMut},
     Result, Status,
 };
 
+/**
+ * Mel filterbank initialization
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MelCoeffs {
+    /**
+     * Bands linearly spaced in the mel scale, from `fmin` to `fmax` (in Hz)
+     */
+    Mel { fmin: f32, fmax: f32 },
+
+    /**
+     * Bands linearly spaced in the Htk mel scale, from `fmin` to `fmax` (in Hz)
+     */
+    Htk { fmin: f32, fmax: f32 },
+
+    /**
+     * Malcolm Slaney's Auditory Toolbox parameters
+     */
+    Slaney,
+}
+
+/**
+ * MFCC configuration
+ *
+ * The optional parameters which are `None` keep the defaults of _aubio_.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MfccConfig {
+    /**
+     * Size of analysis buffer (and length the FFT transform)
+     */
+    pub buf_size: usize,
+
+    /**
+     * Number of desired filters
+     */
+    pub n_filters: usize,
+
+    /**
+     * Number of desired coefficients
+     */
+    pub n_coeffs: usize,
+
+    /**
+     * Audio sampling rate
+     */
+    pub sample_rate: u32,
+
+    /**
+     * Power parameter
+     */
+    pub power: Option<f32>,
+
+    /**
+     * Scaling parameter
+     */
+    pub scale: Option<f32>,
+
+    /**
+     * Mel filterbank initialization
+     */
+    pub mel_coeffs: Option<MelCoeffs>,
+}
+
+impl MfccConfig {
+    /**
+     * Create MFCC configuration with default parameters
+     */
+    pub fn new(buf_size: usize, n_filters: usize, n_coeffs: usize, sample_rate: u32) -> Self {
+        Self {
+            buf_size,
+            n_filters,
+            n_coeffs,
+            sample_rate,
+            power: None,
+            scale: None,
+            mel_coeffs: None,
+        }
+    }
+
+    /**
+     * Create MFCC object using configuration
+     */
+    pub fn build(&self) -> Result<MFCC> {
+        let mut mfcc = MFCC::new(
+            self.buf_size,
+            self.n_filters,
+            self.n_coeffs,
+            self.sample_rate,
+        )?;
+
+        if let Some(power) = self.power {
+            mfcc.set_power(power);
+        }
+        if let Some(scale) = self.scale {
+            mfcc.set_scale(scale);
+        }
+        match self.mel_coeffs {
+            Some(MelCoeffs::Mel { fmin, fmax }) => mfcc.set_mel_coeffs(fmin, fmax),
+            Some(MelCoeffs::Htk { fmin, fmax }) => mfcc.set_mel_coeffs_htk(fmin, fmax),
+            Some(MelCoeffs::Slaney) => mfcc.set_mel_coeffs_slaney(),
+            None => (),
+        }
+
+        Ok(mfcc)
+    }
+}
+
 /**
  * MFCC object
  *
@@ -18,7 +128,10 @@ use crate::{
 pub struct MFCC {
     mfcc: *mut ffi::aubio_mfcc_t,
     buf_size: usize,
+    n_filters: usize,
     n_coeffs: usize,
+    sample_rate: u32,
+    mel_coeffs: Option<MelCoeffs>,
 }
 
 impl Drop for MFCC {
@@ -27,6 +140,8 @@ impl Drop for MFCC {
     }
 }
 
+unsafe impl Send for MFCC {}
+
 impl MFCC {
     /**
      * Create MFCC object
@@ -42,21 +157,27 @@ impl MFCC {
         n_coeffs: usize,
         sample_rate: u32,
     ) -> Result<Self> {
-        let mfcc = unsafe {
+        check_arg("buf_size", buf_size, buf_size >= 1)?;
+        check_arg("n_filters", n_filters, n_filters >= 1)?;
+        check_arg("n_coeffs", n_coeffs, n_coeffs >= 1 && n_coeffs <= n_filters)?;
+        check_sample_rate(sample_rate)?;
+
+        let mfcc = check_init(|| unsafe {
             ffi::new_aubio_mfcc(
                 buf_size as ffi::uint_t,
                 n_filters as ffi::uint_t,
                 n_coeffs as ffi::uint_t,
                 sample_rate as ffi::uint_t,
             )
-        };
-
-        check_init(mfcc)?;
+        })?;
 
         Ok(Self {
             mfcc,
             buf_size,
+            n_filters,
             n_coeffs,
+            sample_rate,
+            mel_coeffs: None,
         })
     }
 
@@ -114,6 +235,21 @@ impl MFCC {
         self
     }
 
+    /**
+     * Get current configuration
+     */
+    pub fn config(&self) -> MfccConfig {
+        MfccConfig {
+            buf_size: self.buf_size,
+            n_filters: self.n_filters,
+            n_coeffs: self.n_coeffs,
+            sample_rate: self.sample_rate,
+            power: Some(self.get_power()),
+            scale: Some(self.get_scale()),
+            mel_coeffs: self.mel_coeffs,
+        }
+    }
+
     /**
      * MFCC object processing
      *
@@ -179,6 +315,7 @@ impl MFCC {
         unsafe {
             ffi::aubio_mfcc_set_mel_coeffs(self.mfcc, fmin, fmax);
         }
+        self.mel_coeffs = Some(MelCoeffs::Mel { fmin, fmax });
     }
 
     /**
@@ -193,6 +330,7 @@ impl MFCC {
         unsafe {
             ffi::aubio_mfcc_set_mel_coeffs_htk(self.mfcc, fmin, fmax);
         }
+        self.mel_coeffs = Some(MelCoeffs::Htk { fmin, fmax });
     }
 
     /**
@@ -206,5 +344,95 @@ impl MFCC {
         unsafe {
             ffi::aubio_mfcc_set_mel_coeffs_slaney(self.mfcc);
         }
+        self.mel_coeffs = Some(MelCoeffs::Slaney);
+    }
+
+    /**
+     * Reset MFCC computation
+     *
+     * Each frame is computed independently from the previous ones,
+     * so there is no history to clear. This exists for symmetry with
+     * the other analyzers, which may be reset between clips.
+     */
+    pub fn reset(&mut self) {}
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test_config() {
+        let mut config = MfccConfig::new(512, 40, 13, 16000);
+        config.scale = Some(2.0);
+        config.mel_coeffs = Some(MelCoeffs::Htk {
+            fmin: 0.0,
+            fmax: 8000.0,
+        });
+
+        let mfcc = config.build().unwrap();
+
+        assert_eq!(mfcc.get_scale(), 2.0);
+
+        let config = mfcc.config();
+        assert_eq!(
+            config.mel_coeffs,
+            Some(MelCoeffs::Htk {
+                fmin: 0.0,
+                fmax: 8000.0
+            })
+        );
+        assert_eq!(config.build().unwrap().config(), config);
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        let invalid = |name, value: &str| {
+            Some(Error::InvalidArg {
+                name,
+                value: value.into(),
+            })
+        };
+
+        assert_eq!(MFCC::new(0, 40, 13, 44100).err(), invalid("buf_size", "0"));
+        assert_eq!(
+            MFCC::new(512, 0, 13, 44100).err(),
+            invalid("n_filters", "0")
+        );
+        assert_eq!(MFCC::new(512, 40, 0, 44100).err(), invalid("n_coeffs", "0"));
+        assert_eq!(
+            MFCC::new(512, 10, 13, 44100).err(),
+            invalid("n_coeffs", "13")
+        );
+        assert_eq!(MFCC::new(512, 40, 13, 0).err(), invalid("sample_rate", "0"));
+    }
+
+    #[test]
+    fn test_reset() {
+        const BUF_S: usize = 512;
+
+        let frame = |hop: usize| {
+            let norm = (0..BUF_S / 2 + 1)
+                .map(|i| ((i + hop) % 9) as f32 + 1.0)
+                .collect::<Vec<_>>();
+            vec::CVecBuf::from_parts(norm, vec![0.0; BUF_S / 2 + 1]).unwrap()
+        };
+
+        let mut mfcc = MFCC::new(BUF_S, 40, 13, 44100).unwrap();
+        let mut first = [0f32; 13];
+        let mut output = [0f32; 13];
+
+        mfcc.do_(frame(0).as_cvec(), first.as_mut()).unwrap();
+        for hop in 1..4 {
+            mfcc.do_(frame(hop).as_cvec(), output.as_mut()).unwrap();
+        }
+
+        // frames are computed independently, so the output has no history
+        mfcc.do_(frame(0).as_cvec(), output.as_mut()).unwrap();
+        assert_eq!(output, first);
+
+        mfcc.reset();
+        mfcc.do_(frame(0).as_cvec(), output.as_mut()).unwrap();
+        assert_eq!(output, first);
     }
 }
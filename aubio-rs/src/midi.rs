@@ -0,0 +1,823 @@
+use crate::{NoteChange, NoteEvent, Tempo};
+
+use std::ops::Deref;
+
+/**
+ * Convert velocity of detected note to MIDI velocity
+ *
+ * The velocity which _aubio_ reports is `127` plus the level of note in dB,
+ * so it is rounded and clamped to the range of note-on velocities `1..=127`.
+ */
+pub fn midi_velocity(velocity: f32) -> u8 {
+    velocity.round().clamp(1.0, 127.0) as u8
+}
+
+/**
+ * Convert pitch of detected note to MIDI note number
+ */
+pub fn midi_note(pitch: f32) -> u8 {
+    pitch.round().clamp(0.0, 127.0) as u8
+}
+
+/**
+ * Number of MIDI clock ticks per quarter note
+ */
+pub const MIDI_CLOCK_PPQN: u32 = 24;
+
+/**
+ * The value of pitch bend which means no bend
+ */
+pub const PITCH_BEND_CENTER: u16 = 0x2000;
+
+/**
+ * Default range of pitch bend, in semitones
+ */
+pub const DEFAULT_BEND_RANGE: f32 = 2.0;
+
+/**
+ * MIDI 1.0 message
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MidiMessage {
+    /**
+     * Note on
+     */
+    NoteOn { channel: u8, note: u8, velocity: u8 },
+
+    /**
+     * Note off
+     */
+    NoteOff { channel: u8, note: u8, velocity: u8 },
+
+    /**
+     * Pitch bend (`0..0x4000`, where `0x2000` means no bend)
+     */
+    PitchBend { channel: u8, value: u16 },
+
+    /**
+     * Timing clock (24 ticks per quarter note)
+     */
+    Clock,
+
+    /**
+     * Start the sequence
+     */
+    Start,
+
+    /**
+     * Continue the sequence
+     */
+    Continue,
+
+    /**
+     * Stop the sequence
+     */
+    Stop,
+}
+
+impl MidiMessage {
+    /**
+     * Encode message
+     */
+    pub fn to_bytes(&self) -> MidiBytes {
+        use self::MidiMessage::*;
+
+        match *self {
+            NoteOn {
+                channel,
+                note,
+                velocity,
+            } => MidiBytes::new(&[0x90 | channel & 0x0f, note & 0x7f, velocity & 0x7f]),
+            NoteOff {
+                channel,
+                note,
+                velocity,
+            } => MidiBytes::new(&[0x80 | channel & 0x0f, note & 0x7f, velocity & 0x7f]),
+            PitchBend { channel, value } => MidiBytes::new(&[
+                0xe0 | channel & 0x0f,
+                (value & 0x7f) as u8,
+                (value >> 7 & 0x7f) as u8,
+            ]),
+            Clock => MidiBytes::new(&[0xf8]),
+            Start => MidiBytes::new(&[0xfa]),
+            Continue => MidiBytes::new(&[0xfb]),
+            Stop => MidiBytes::new(&[0xfc]),
+        }
+    }
+
+    /**
+     * Decode message
+     *
+     * Returns `None` when the message is not supported or incomplete.
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        use self::MidiMessage::*;
+
+        let status = *bytes.first()?;
+        let channel = status & 0x0f;
+
+        Some(match (status, bytes.len()) {
+            (0xf8, 1) => Clock,
+            (0xfa, 1) => Start,
+            (0xfb, 1) => Continue,
+            (0xfc, 1) => Stop,
+            (0x80..=0xef, 3) if bytes[1] < 0x80 && bytes[2] < 0x80 => match status & 0xf0 {
+                0x80 => NoteOff {
+                    channel,
+                    note: bytes[1],
+                    velocity: bytes[2],
+                },
+                0x90 => NoteOn {
+                    channel,
+                    note: bytes[1],
+                    velocity: bytes[2],
+                },
+                0xe0 => PitchBend {
+                    channel,
+                    value: bytes[1] as u16 | (bytes[2] as u16) << 7,
+                },
+                _ => return None,
+            },
+            _ => return None,
+        })
+    }
+}
+
+/**
+ * Encoded MIDI message
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MidiBytes {
+    data: [u8; 3],
+    len: usize,
+}
+
+impl MidiBytes {
+    fn new(bytes: &[u8]) -> Self {
+        let mut data = [0u8; 3];
+        data[..bytes.len()].copy_from_slice(bytes);
+
+        Self {
+            data,
+            len: bytes.len(),
+        }
+    }
+}
+
+impl Deref for MidiBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+}
+
+impl AsRef<[u8]> for MidiBytes {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+/**
+ * MIDI message with its position
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MidiEvent {
+    /**
+     * Position of message, in samples
+     */
+    pub position: usize,
+
+    /**
+     * MIDI message
+     */
+    pub message: MidiMessage,
+}
+
+/**
+ * Iterator over MIDI clock ticks
+ */
+#[derive(Debug, Clone)]
+pub struct ClockTicks {
+    next: f64,
+    step: f64,
+    count: usize,
+}
+
+impl ClockTicks {
+    fn empty() -> Self {
+        Self {
+            next: 0.0,
+            step: 0.0,
+            count: 0,
+        }
+    }
+}
+
+impl Iterator for ClockTicks {
+    type Item = MidiEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let position = self.next.round() as usize;
+        self.next += self.step;
+        self.count -= 1;
+
+        Some(MidiEvent {
+            position,
+            message: MidiMessage::Clock,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.count, Some(self.count))
+    }
+}
+
+impl ExactSizeIterator for ClockTicks {}
+
+/**
+ * Live MIDI messages encoder
+ *
+ * Converts the outputs of `Notes`, `Tempo` and `Pitch` into MIDI messages,
+ * which are stamped by the positions in samples of input signal.
+ * It does not allocate, so it can be used in real-time audio processing.
+ *
+ * ```no_run
+ * # extern crate aubio_lib;
+ * use aubio_rs::{MidiEncoder, Notes, OnsetMode, Tempo};
+ *
+ * const HOP_SIZE: usize = 256;
+ *
+ * let mut notes = Notes::new(512, HOP_SIZE, 44100).unwrap();
+ * let mut tempo = Tempo::new(OnsetMode::SpecFlux, 1024, HOP_SIZE, 44100).unwrap();
+ * let mut midi = MidiEncoder::new(44100);
+ *
+ * let samples = vec![0f32; 44100];
+ *
+ * for (index, hop) in samples.chunks(HOP_SIZE).enumerate() {
+ *     let position = index * HOP_SIZE;
+ *
+ *     let change = notes.do_change(hop).unwrap();
+ *     for event in midi.note_change(position, change) {
+ *         println!("{}: {:?}", event.position, event.message.to_bytes().as_ref());
+ *     }
+ *
+ *     let beat = tempo.do_result(hop).unwrap();
+ *     for event in midi.tempo(position, &tempo, beat) {
+ *         println!("{}: {:?}", event.position, event.message.to_bytes().as_ref());
+ *     }
+ * }
+ * ```
+ */
+#[derive(Debug, Clone)]
+pub struct MidiEncoder {
+    sample_rate: u32,
+    channel: u8,
+    bend_range: f32,
+    note: Option<u8>,
+    bend: Option<u16>,
+    next_tick: Option<f64>,
+}
+
+impl MidiEncoder {
+    /**
+     * Create MIDI encoder
+     *
+     * - `sample_rate` Sampling rate of input signal
+     */
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            channel: 0,
+            bend_range: DEFAULT_BEND_RANGE,
+            note: None,
+            bend: None,
+            next_tick: None,
+        }
+    }
+
+    /**
+     * Set MIDI channel
+     */
+    pub fn with_channel(mut self, channel: u8) -> Self {
+        self.set_channel(channel);
+        self
+    }
+
+    /**
+     * Set range of pitch bend, in semitones
+     */
+    pub fn with_bend_range(mut self, bend_range: f32) -> Self {
+        self.set_bend_range(bend_range);
+        self
+    }
+
+    /**
+     * Get MIDI channel
+     */
+    pub fn get_channel(&self) -> u8 {
+        self.channel
+    }
+
+    /**
+     * Set MIDI channel (0..16)
+     */
+    pub fn set_channel(&mut self, channel: u8) {
+        self.channel = channel & 0x0f;
+    }
+
+    /**
+     * Get range of pitch bend, in semitones
+     */
+    pub fn get_bend_range(&self) -> f32 {
+        self.bend_range
+    }
+
+    /**
+     * Set range of pitch bend, in semitones
+     *
+     * It should be the same as the pitch bend sensitivity of receiver.
+     */
+    pub fn set_bend_range(&mut self, bend_range: f32) {
+        self.bend_range = bend_range;
+    }
+
+    /**
+     * Get the note which is sounding now
+     */
+    pub fn get_note(&self) -> Option<u8> {
+        self.note
+    }
+
+    /**
+     * Convert notes which have been changed
+     *
+     * - `position` Position of hop which notes have been detected in, in samples
+     * - `change` The output of `Notes::do_change`
+     *
+     * The note-off goes before the note-on.
+     */
+    pub fn note_change(
+        &mut self,
+        position: usize,
+        change: NoteChange,
+    ) -> impl Iterator<Item = MidiEvent> {
+        let channel = self.channel;
+
+        let off = change.off.map(|note| {
+            let note = midi_note(note.pitch);
+
+            if self.note == Some(note) {
+                self.note = None;
+            }
+
+            MidiMessage::NoteOff {
+                channel,
+                note,
+                velocity: 0,
+            }
+        });
+
+        let on = change.on.map(|note| {
+            let velocity = midi_velocity(note.velocity);
+            let note = midi_note(note.pitch);
+
+            self.note = Some(note);
+
+            MidiMessage::NoteOn {
+                channel,
+                note,
+                velocity,
+            }
+        });
+
+        off.into_iter()
+            .chain(on)
+            .map(move |message| MidiEvent { position, message })
+    }
+
+    /**
+     * Convert complete note into note-on and note-off
+     */
+    pub fn note_event(&self, note: &NoteEvent) -> [MidiEvent; 2] {
+        let channel = self.channel;
+
+        [
+            MidiEvent {
+                position: note.start,
+                message: MidiMessage::NoteOn {
+                    channel,
+                    note: note.midi_note,
+                    velocity: midi_velocity(note.velocity),
+                },
+            },
+            MidiEvent {
+                position: note.end,
+                message: MidiMessage::NoteOff {
+                    channel,
+                    note: note.midi_note,
+                    velocity: 0,
+                },
+            },
+        ]
+    }
+
+    /**
+     * Convert detected pitch into pitch bend
+     *
+     * - `position` Position of hop which pitch has been detected in, in samples
+     * - `pitch` Detected pitch in MIDI units (see `PitchUnit::Midi`), or zero when there is no pitch
+     *
+     * The bend is the deviation of pitch from the sounding note (or from the nearest one when
+     * no notes are sounding). Returns `None` when there is no pitch or the bend is not changed.
+     */
+    pub fn pitch_bend(&mut self, position: usize, pitch: f32) -> Option<MidiEvent> {
+        if pitch <= 0.0 || self.bend_range <= 0.0 {
+            return None;
+        }
+
+        let note = self.note.map(f32::from).unwrap_or_else(|| pitch.round());
+        let bend = ((pitch - note) / self.bend_range).clamp(-1.0, 1.0);
+        let value = (PITCH_BEND_CENTER as f32 * (1.0 + bend))
+            .round()
+            .min(0x3fff as f32) as u16;
+
+        if self.bend == Some(value) {
+            return None;
+        }
+
+        self.bend = Some(value);
+
+        Some(MidiEvent {
+            position,
+            message: MidiMessage::PitchBend {
+                channel: self.channel,
+                value,
+            },
+        })
+    }
+
+    /**
+     * Convert detected pitch in Hz (see `PitchUnit::Hz`) into pitch bend
+     */
+    pub fn pitch_bend_hz(&mut self, position: usize, freq: f32) -> Option<MidiEvent> {
+        if freq <= 0.0 {
+            return None;
+        }
+
+        self.pitch_bend(position, 69.0 + 12.0 * (freq / 440.0).log2())
+    }
+
+    /**
+     * Generate MIDI clock ticks for hop
+     *
+     * - `position` Position of hop, in samples
+     * - `hop_size` Size of hop, in samples
+     * - `bpm` Current tempo
+     * - `beat` Position of beat which has been detected in this hop, in samples
+     *
+     * The ticks are started with the first beat and aligned to each of following beats.
+     */
+    pub fn clock(
+        &mut self,
+        position: usize,
+        hop_size: usize,
+        bpm: f32,
+        beat: Option<usize>,
+    ) -> ClockTicks {
+        if bpm <= 0.0 {
+            return ClockTicks::empty();
+        }
+
+        let step = self.sample_rate as f64 * 60.0 / (bpm as f64 * MIDI_CLOCK_PPQN as f64);
+        let start = position as f64;
+        let end = (position + hop_size) as f64;
+
+        let next = match (beat, self.next_tick) {
+            (Some(beat), _) => {
+                let beat = beat as f64;
+
+                if beat < start {
+                    // beat has been reported late, so skip the ticks which are gone
+                    beat + ((start - beat) / step).ceil() * step
+                } else {
+                    beat
+                }
+            }
+            (None, Some(next)) => next,
+            (None, None) => return ClockTicks::empty(),
+        };
+
+        let count = if next < end {
+            ((end - next) / step).ceil() as usize
+        } else {
+            0
+        };
+
+        self.next_tick = Some(next + count as f64 * step);
+
+        ClockTicks { next, step, count }
+    }
+
+    /**
+     * Generate MIDI clock ticks for hop using tempo detection
+     *
+     * - `position` Position of hop, in samples
+     * - `tempo` Tempo detection object
+     * - `beat` The output of `Tempo::do_result` for this hop
+     */
+    pub fn tempo(&mut self, position: usize, tempo: &Tempo, beat: f32) -> ClockTicks {
+        let beat = if beat != 0.0 {
+            Some(tempo.get_last())
+        } else {
+            None
+        };
+
+        self.clock(position, tempo.get_hop(), tempo.get_bpm(), beat)
+    }
+
+    /**
+     * Reset encoder state
+     *
+     * The sounding note is forgotten and the clock waits for the next beat.
+     */
+    pub fn reset(&mut self) {
+        self.note = None;
+        self.bend = None;
+        self.next_tick = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test_velocity() {
+        assert_eq!(midi_velocity(127.0), 127);
+        assert_eq!(midi_velocity(140.0), 127);
+        assert_eq!(midi_velocity(99.6), 100);
+        assert_eq!(midi_velocity(0.0), 1);
+        assert_eq!(midi_velocity(-20.0), 1);
+        assert_eq!(midi_note(59.7), 60);
+    }
+
+    #[test]
+    fn test_messages() {
+        let messages = [
+            (
+                MidiMessage::NoteOn {
+                    channel: 1,
+                    note: 60,
+                    velocity: 100,
+                },
+                &[0x91, 60, 100][..],
+            ),
+            (
+                MidiMessage::NoteOff {
+                    channel: 0,
+                    note: 60,
+                    velocity: 0,
+                },
+                &[0x80, 60, 0],
+            ),
+            (
+                MidiMessage::PitchBend {
+                    channel: 2,
+                    value: 0x3fff,
+                },
+                &[0xe2, 0x7f, 0x7f],
+            ),
+            (
+                MidiMessage::PitchBend {
+                    channel: 0,
+                    value: PITCH_BEND_CENTER,
+                },
+                &[0xe0, 0x00, 0x40],
+            ),
+            (MidiMessage::Clock, &[0xf8]),
+            (MidiMessage::Start, &[0xfa]),
+            (MidiMessage::Stop, &[0xfc]),
+        ];
+
+        for (message, bytes) in messages.iter() {
+            assert_eq!(&*message.to_bytes(), *bytes);
+            assert_eq!(MidiMessage::from_bytes(bytes), Some(*message));
+        }
+
+        assert_eq!(MidiMessage::from_bytes(&[]), None);
+        assert_eq!(MidiMessage::from_bytes(&[0x90, 60]), None);
+        assert_eq!(MidiMessage::from_bytes(&[0xb0, 7, 100]), None);
+    }
+
+    #[test]
+    fn test_notes() {
+        let mut midi = MidiEncoder::new(44100).with_channel(3);
+
+        let change = NoteChange {
+            off: None,
+            on: Some(Note {
+                pitch: 60.2,
+                velocity: 110.0,
+            }),
+        };
+        assert_eq!(
+            midi.note_change(256, change).collect::<Vec<_>>(),
+            vec![MidiEvent {
+                position: 256,
+                message: MidiMessage::NoteOn {
+                    channel: 3,
+                    note: 60,
+                    velocity: 110
+                }
+            }]
+        );
+        assert_eq!(midi.get_note(), Some(60));
+
+        let change = NoteChange {
+            off: Some(Note {
+                pitch: 60.2,
+                velocity: 0.0,
+            }),
+            on: None,
+        };
+        assert_eq!(
+            midi.note_change(512, change).collect::<Vec<_>>(),
+            vec![MidiEvent {
+                position: 512,
+                message: MidiMessage::NoteOff {
+                    channel: 3,
+                    note: 60,
+                    velocity: 0
+                }
+            }]
+        );
+        assert_eq!(midi.get_note(), None);
+        assert_eq!(midi.note_change(768, NoteChange::default()).count(), 0);
+
+        let [on, off] = midi.note_event(&NoteEvent {
+            midi_note: 64,
+            start: 100,
+            end: 200,
+            velocity: 90.0,
+            pitch_hz: 329.6,
+        });
+        assert_eq!(on.position, 100);
+        assert_eq!(off.position, 200);
+        assert_eq!(off.message.to_bytes().as_ref(), &[0x83, 64, 0]);
+    }
+
+    #[test]
+    fn test_pitch_bend() {
+        let mut midi = MidiEncoder::new(44100);
+
+        // no pitch
+        assert_eq!(midi.pitch_bend(0, 0.0), None);
+        // the nearest note
+        assert_eq!(
+            midi.pitch_bend(0, 60.0).map(|event| event.message),
+            Some(MidiMessage::PitchBend {
+                channel: 0,
+                value: PITCH_BEND_CENTER
+            })
+        );
+        // not changed
+        assert_eq!(midi.pitch_bend(256, 60.0), None);
+
+        let change = NoteChange {
+            off: None,
+            on: Some(Note {
+                pitch: 60.0,
+                velocity: 100.0,
+            }),
+        };
+        midi.note_change(512, change).for_each(drop);
+
+        // +1 semitone of 2
+        assert_eq!(
+            midi.pitch_bend(768, 61.0),
+            Some(MidiEvent {
+                position: 768,
+                message: MidiMessage::PitchBend {
+                    channel: 0,
+                    value: 0x3000
+                }
+            })
+        );
+        // -50 cents
+        assert_eq!(
+            midi.pitch_bend(1024, 59.5).map(|event| event.message),
+            Some(MidiMessage::PitchBend {
+                channel: 0,
+                value: 0x1800
+            })
+        );
+        // out of range
+        assert_eq!(
+            midi.pitch_bend(1280, 48.0).map(|event| event.message),
+            Some(MidiMessage::PitchBend {
+                channel: 0,
+                value: 0
+            })
+        );
+        // 440Hz against 60
+        assert_eq!(
+            midi.pitch_bend_hz(1536, 440.0).map(|event| event.message),
+            Some(MidiMessage::PitchBend {
+                channel: 0,
+                value: 0x3fff
+            })
+        );
+        assert_eq!(midi.pitch_bend_hz(1792, 0.0), None);
+    }
+
+    #[test]
+    fn test_clock() {
+        // 1000 samples per beat at 60 bpm, so the tick is 1000 / 24 samples
+        const SR: u32 = 1000;
+        const HOP_S: usize = 100;
+
+        let mut midi = MidiEncoder::new(SR);
+
+        // no ticks before the first beat
+        assert_eq!(midi.clock(0, HOP_S, 60.0, None).count(), 0);
+        // no ticks without tempo
+        assert_eq!(midi.clock(100, HOP_S, 0.0, Some(150)).count(), 0);
+
+        let ticks = midi.clock(200, HOP_S, 60.0, Some(250)).collect::<Vec<_>>();
+        assert_eq!(
+            ticks.iter().map(|tick| tick.position).collect::<Vec<_>>(),
+            vec![250, 292]
+        );
+        assert!(ticks.iter().all(|tick| tick.message == MidiMessage::Clock));
+
+        let mut positions = Vec::new();
+        for hop in 3..12 {
+            positions.extend(
+                midi.clock(hop * HOP_S, HOP_S, 60.0, None)
+                    .map(|tick| tick.position),
+            );
+        }
+        assert_eq!(positions.len(), 21);
+        assert_eq!(positions[0], 333);
+        assert_eq!(positions[20], 1167);
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // the late beat realigns the ticks
+        let ticks = midi
+            .clock(1200, HOP_S, 60.0, Some(1240))
+            .map(|tick| tick.position)
+            .collect::<Vec<_>>();
+        assert_eq!(ticks, vec![1240, 1282]);
+
+        let ticks = midi
+            .clock(1300, HOP_S, 60.0, Some(1240))
+            .map(|tick| tick.position)
+            .collect::<Vec<_>>();
+        assert_eq!(ticks, vec![1323, 1365]);
+
+        midi.reset();
+        assert_eq!(midi.clock(1400, HOP_S, 60.0, None).count(), 0);
+    }
+
+    #[test]
+    fn test_no_alloc() {
+        let mut midi = MidiEncoder::new(44100);
+        let change = NoteChange {
+            off: Some(Note {
+                pitch: 60.0,
+                velocity: 0.0,
+            }),
+            on: Some(Note {
+                pitch: 62.0,
+                velocity: 100.0,
+            }),
+        };
+
+        let (count, allocs) = crate::test::count_allocs(|| {
+            let mut count = 0;
+            for hop in 0..64 {
+                let position = hop * 256;
+                count += midi.note_change(position, change).count();
+                count += midi
+                    .pitch_bend(position, 62.0 + hop as f32 / 100.0)
+                    .iter()
+                    .count();
+                count += midi
+                    .clock(position, 256, 120.0, Some(position))
+                    .map(|tick| tick.message.to_bytes().len())
+                    .sum::<usize>();
+            }
+            count
+        });
+
+        assert!(count > 0);
+        assert_eq!(allocs, 0);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    check_init, ffi,
+    check_buf_hop, check_init, check_sample_rate, ffi, midi_note,
     vec::{FVec, FVecMut},
     Result, Status,
 };
@@ -8,6 +8,7 @@ use crate::{
  * Recognized note data
  */
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Note {
     pub pitch: f32,
     pub velocity: f32,
@@ -15,23 +16,258 @@ pub struct Note {
 
 impl Note {
     fn parse(values: &[f32; 3]) -> Vec<Self> {
-        let mut notes = Vec::new();
+        NoteChange::parse(values).iter().collect()
+    }
+}
 
-        if values[2] != 0.0 {
-            notes.push(Self {
-                pitch: values[2],
-                velocity: 0.0,
-            });
+/**
+ * Notes which have been changed in single frame
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoteChange {
+    /**
+     * The note to turn off (its velocity is zero)
+     */
+    pub off: Option<Note>,
+
+    /**
+     * The note to turn on
+     */
+    pub on: Option<Note>,
+}
+
+impl NoteChange {
+    fn parse(values: &[f32; 3]) -> Self {
+        Self {
+            off: if values[2] != 0.0 {
+                Some(Note {
+                    pitch: values[2],
+                    velocity: 0.0,
+                })
+            } else {
+                None
+            },
+            on: if values[0] != 0.0 {
+                Some(Note {
+                    pitch: values[0],
+                    velocity: values[1],
+                })
+            } else {
+                None
+            },
         }
+    }
+
+    /**
+     * Check that no notes has been changed
+     */
+    pub fn is_empty(&self) -> bool {
+        self.off.is_none() && self.on.is_none()
+    }
+
+    /**
+     * Iterate over changed notes, the note-off goes first
+     */
+    pub fn iter(&self) -> impl Iterator<Item = Note> {
+        self.off.into_iter().chain(self.on)
+    }
+}
+
+/**
+ * Number of hops which _aubio_ uses to estimate the pitch of a new note
+ *
+ * The note is reported when the median pitch over this window is known,
+ * so it is delayed by `NOTES_MEDIAN - 1` hops from its onset.
+ */
+const NOTES_MEDIAN: usize = 6;
+
+/**
+ * Complete note with its timing
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoteEvent {
+    /**
+     * Midi note number
+     */
+    pub midi_note: u8,
+
+    /**
+     * Position of note start, in samples
+     */
+    pub start: usize,
+
+    /**
+     * Position of note end, in samples
+     */
+    pub end: usize,
 
-        if values[0] != 0.0 {
-            notes.push(Self {
-                pitch: values[0],
-                velocity: values[1],
+    /**
+     * Note velocity, as reported by _aubio_
+     */
+    pub velocity: f32,
+
+    /**
+     * Note frequency, in Hz
+     */
+    pub pitch_hz: f32,
+}
+
+impl NoteEvent {
+    /**
+     * Get note duration, in samples
+     */
+    pub fn duration(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+/**
+ * Note which is sounding now
+ */
+#[derive(Debug, Clone, Copy)]
+struct OpenNote {
+    note: Note,
+    start: usize,
+}
+
+/**
+ * Pairing of note-ons with note-offs
+ */
+#[derive(Debug, Clone, Copy)]
+struct NoteState {
+    hop_size: usize,
+    delay: usize,
+    position: usize,
+    current: Option<OpenNote>,
+}
+
+impl NoteState {
+    fn new(hop_size: usize) -> Self {
+        Self {
+            hop_size,
+            delay: (NOTES_MEDIAN - 1) * hop_size,
+            position: 0,
+            current: None,
+        }
+    }
+
+    /**
+     * Apply notes which have been detected in the next hop
+     */
+    fn update(&mut self, notes: NoteChange) -> Option<NoteEvent> {
+        let position = self.position;
+        self.position += self.hop_size;
+
+        let end = match notes.on {
+            // the note is replaced by the new one, which has been reported with delay
+            Some(_) => position.saturating_sub(self.delay),
+            // the note has been released
+            None if notes.off.is_some() => position,
+            None => return None,
+        };
+
+        let event = self.close(end);
+
+        if let Some(note) = notes.on {
+            self.current = Some(OpenNote {
+                note,
+                start: position.saturating_sub(self.delay),
             });
         }
 
-        notes
+        event
+    }
+
+    fn close(&mut self, end: usize) -> Option<NoteEvent> {
+        self.current
+            .take()
+            .map(|OpenNote { note, start }| NoteEvent {
+                midi_note: midi_note(note.pitch),
+                start,
+                end: end.max(start),
+                velocity: note.velocity,
+                pitch_hz: 440.0 * 2f32.powf((note.pitch - 69.0) / 12.0),
+            })
+    }
+
+    fn finish(&mut self) -> Option<NoteEvent> {
+        self.close(self.position)
+    }
+}
+
+/**
+ * Notes detection configuration
+ *
+ * The optional parameters which are `None` keep the defaults of _aubio_.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NotesConfig {
+    /**
+     * Buffer size for phase vocoder
+     */
+    pub buf_size: usize,
+
+    /**
+     * Hop size for phase vocoder
+     */
+    pub hop_size: usize,
+
+    /**
+     * Sampling rate of the input signal
+     */
+    pub sample_rate: u32,
+
+    /**
+     * Silence threshold, in dB
+     */
+    pub silence: Option<f32>,
+
+    /**
+     * Minimum inter-onset interval, in milliseconds
+     */
+    pub minioi_ms: Option<f32>,
+
+    /**
+     * Release drop level, in dB
+     */
+    pub release_drop: Option<f32>,
+}
+
+impl NotesConfig {
+    /**
+     * Create notes detection configuration with default parameters
+     */
+    pub fn new(buf_size: usize, hop_size: usize, sample_rate: u32) -> Self {
+        Self {
+            buf_size,
+            hop_size,
+            sample_rate,
+            silence: None,
+            minioi_ms: None,
+            release_drop: None,
+        }
+    }
+
+    /**
+     * Create notes detection object using configuration
+     */
+    pub fn build(&self) -> Result<Notes> {
+        let mut notes = Notes::new(self.buf_size, self.hop_size, self.sample_rate)?;
+
+        if let Some(silence) = self.silence {
+            notes.set_silence(silence);
+        }
+        if let Some(minioi) = self.minioi_ms {
+            notes.set_minioi_ms(minioi);
+        }
+        if let Some(release_drop) = self.release_drop {
+            notes.set_release_drop(release_drop);
+        }
+
+        Ok(notes)
     }
 }
 
@@ -40,7 +276,9 @@ impl Note {
  */
 pub struct Notes {
     notes: *mut ffi::aubio_notes_t,
+    buf_size: usize,
     hop_size: usize,
+    sample_rate: u32,
 }
 
 impl Drop for Notes {
@@ -49,6 +287,8 @@ impl Drop for Notes {
     }
 }
 
+unsafe impl Send for Notes {}
+
 impl Notes {
     /**
      * Create notes detection object
@@ -58,18 +298,24 @@ impl Notes {
      * - `samplerate` Sampling rate of the input signal
      */
     pub fn new(buf_size: usize, hop_size: usize, sample_rate: u32) -> Result<Self> {
-        let notes = unsafe {
+        check_buf_hop(buf_size, hop_size)?;
+        check_sample_rate(sample_rate)?;
+
+        let notes = check_init(|| unsafe {
             ffi::new_aubio_notes(
                 "default\0".as_ptr() as *const _,
                 buf_size as ffi::uint_t,
                 hop_size as ffi::uint_t,
                 sample_rate as ffi::uint_t,
             )
-        };
+        })?;
 
-        check_init(notes)?;
-
-        Ok(Self { notes, hop_size })
+        Ok(Self {
+            notes,
+            buf_size,
+            hop_size,
+            sample_rate,
+        })
     }
 
     /**
@@ -103,6 +349,20 @@ impl Notes {
         self.hop_size
     }
 
+    /**
+     * Get current configuration
+     */
+    pub fn config(&self) -> NotesConfig {
+        NotesConfig {
+            buf_size: self.buf_size,
+            hop_size: self.hop_size,
+            sample_rate: self.sample_rate,
+            silence: Some(self.get_silence()),
+            minioi_ms: Some(self.get_minioi_ms()),
+            release_drop: Some(self.get_release_drop()),
+        }
+    }
+
     /**
      * Execute note detection on an input signal frame
      *
@@ -142,6 +402,20 @@ impl Notes {
         Ok(Note::parse(&output))
     }
 
+    /**
+     * Execute note detection on an input signal frame
+     *
+     * Unlike `do_result()` it does not allocate, so it can be used in real-time audio processing.
+     */
+    pub fn do_change<'i, I>(&mut self, input: I) -> Result<NoteChange>
+    where
+        I: Into<FVec<'i>>,
+    {
+        let mut output = [0f32; 3];
+        self.do_(input, output.as_mut())?;
+        Ok(NoteChange::parse(&output))
+    }
+
     /**
      * Set notes detection silence threshold
      */
@@ -189,4 +463,322 @@ impl Notes {
     pub fn get_release_drop(&self) -> f32 {
         unsafe { ffi::aubio_notes_get_release_drop(self.notes) }
     }
+
+    /**
+     * Reset notes detection
+     *
+     * The pending note and onset history are cleared by recreating the detection object
+     * with the same sizes, silence threshold, minimum inter-onset interval and release drop.
+     */
+    pub fn reset(&mut self) -> Status {
+        *self = self.config().build()?;
+        Ok(())
+    }
+}
+
+/**
+ * Notes tracker
+ *
+ * Runs notes detection and pairs each note-on with the following note-off,
+ * so that complete notes with their start and end positions are emitted.
+ *
+ * The note-ons are reported by _aubio_ few hops after the actual onsets,
+ * so the tracker moves note starts back by the delay (see `set_delay()`).
+ *
+ * ```no_run
+ * # extern crate aubio_lib;
+ * use aubio_rs::{Notes, NoteTracker};
+ *
+ * let samples = vec![0f32; 44100];
+ * let mut tracker = NoteTracker::new(Notes::new(512, 256, 44100).unwrap());
+ *
+ * for hop in samples.chunks(256) {
+ *     if let Some(note) = tracker.do_result(hop).unwrap() {
+ *         println!("{} from {} to {}", note.midi_note, note.start, note.end);
+ *     }
+ * }
+ * if let Some(note) = tracker.finish() {
+ *     println!("{} from {} to {}", note.midi_note, note.start, note.end);
+ * }
+ * ```
+ */
+pub struct NoteTracker {
+    notes: Notes,
+    state: NoteState,
+}
+
+impl NoteTracker {
+    /**
+     * Create notes tracker using notes detection object
+     */
+    pub fn new(notes: Notes) -> Self {
+        let state = NoteState::new(notes.get_hop());
+
+        Self { notes, state }
+    }
+
+    /**
+     * Set delay of note-ons, in samples
+     */
+    pub fn with_delay(mut self, delay: usize) -> Self {
+        self.set_delay(delay);
+        self
+    }
+
+    /**
+     * Get hop size
+     */
+    pub fn get_hop(&self) -> usize {
+        self.notes.get_hop()
+    }
+
+    /**
+     * Get the underlying notes detection object
+     */
+    pub fn notes(&self) -> &Notes {
+        &self.notes
+    }
+
+    /**
+     * Unwrap the underlying notes detection object
+     */
+    pub fn into_notes(self) -> Notes {
+        self.notes
+    }
+
+    /**
+     * Get delay of note-ons, in samples
+     */
+    pub fn get_delay(&self) -> usize {
+        self.state.delay
+    }
+
+    /**
+     * Set delay of note-ons, in samples
+     *
+     * By default it is `5 * hop_size`, because _aubio_ estimates the pitch of a new note
+     * over 6 hops starting with the onset one.
+     */
+    pub fn set_delay(&mut self, delay: usize) {
+        self.state.delay = delay;
+    }
+
+    /**
+     * Get position of the next hop, in samples
+     */
+    pub fn get_position(&self) -> usize {
+        self.state.position
+    }
+
+    /**
+     * Execute notes tracking on an input signal frame
+     *
+     * Returns the note which has been completed in this frame.
+     */
+    pub fn do_result<'i, I>(&mut self, input: I) -> Result<Option<NoteEvent>>
+    where
+        I: Into<FVec<'i>>,
+    {
+        let notes = self.notes.do_change(input)?;
+        Ok(self.state.update(notes))
+    }
+
+    /**
+     * Complete the note which is still sounding at the end of stream
+     */
+    pub fn finish(&mut self) -> Option<NoteEvent> {
+        self.state.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NoteState;
+    use crate::*;
+
+    #[test]
+    fn test_config() {
+        let mut config = NotesConfig::new(512, 256, 44100);
+        config.silence = Some(-50.0);
+        config.release_drop = Some(20.0);
+
+        let notes = config.build().unwrap();
+
+        assert_eq!(notes.get_silence(), -50.0);
+        assert_eq!(notes.get_release_drop(), 20.0);
+
+        let config = notes.config();
+        assert_eq!(config.build().unwrap().config(), config);
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        let invalid = |name, value: &str| {
+            Some(Error::InvalidArg {
+                name,
+                value: value.into(),
+            })
+        };
+
+        assert_eq!(Notes::new(512, 0, 44100).err(), invalid("hop_size", "0"));
+        assert_eq!(Notes::new(1, 1, 44100).err(), invalid("buf_size", "1"));
+        assert_eq!(
+            Notes::new(256, 512, 44100).err(),
+            invalid("hop_size", "512")
+        );
+        assert_eq!(Notes::new(512, 256, 0).err(), invalid("sample_rate", "0"));
+        if !cfg!(feature = "with-fftw3") {
+            assert_eq!(
+                Notes::new(500, 250, 44100).err(),
+                invalid("buf_size", "500")
+            );
+        }
+    }
+
+    #[test]
+    fn test_note_events() {
+        let on = |pitch, velocity| NoteChange::parse(&[pitch, velocity, 0.0]);
+        let off = |pitch| NoteChange::parse(&[0.0, 0.0, pitch]);
+
+        let mut state = NoteState::new(256);
+        state.delay = 512;
+
+        assert_eq!(state.update(NoteChange::default()), None);
+        assert_eq!(state.update(NoteChange::default()), None);
+        assert_eq!(state.update(NoteChange::default()), None);
+        // note-on reported at 768 is started at 256
+        assert_eq!(state.update(on(69.0, 100.0)), None);
+        assert_eq!(state.update(NoteChange::default()), None);
+        // note replaced at 1280 is ended at 768
+        let event = state
+            .update(NoteChange {
+                off: off(69.0).off,
+                on: on(72.2, 90.0).on,
+            })
+            .unwrap();
+        assert_eq!(event.midi_note, 69);
+        assert_eq!(event.start, 256);
+        assert_eq!(event.end, 768);
+        assert_eq!(event.duration(), 512);
+        assert_eq!(event.velocity, 100.0);
+        assert_eq!(event.pitch_hz, 440.0);
+        // note released at 1536
+        let event = state.update(off(72.2)).unwrap();
+        assert_eq!(event.midi_note, 72);
+        assert_eq!(event.start, 768);
+        assert_eq!(event.end, 1536);
+        // nothing to release
+        assert_eq!(state.update(off(72.2)), None);
+        assert_eq!(state.finish(), None);
+        // note is completed at the end of stream
+        assert_eq!(state.update(on(60.0, 80.0)), None);
+        let event = state.finish().unwrap();
+        assert_eq!(event.start, 1536);
+        assert_eq!(event.end, 2304);
+        assert_eq!(state.finish(), None);
+    }
+
+    #[test]
+    fn test_tracker() {
+        const HOP_S: usize = 256;
+
+        let mut tracker = NoteTracker::new(Notes::new(HOP_S * 2, HOP_S, 44100).unwrap());
+        assert_eq!(tracker.get_delay(), HOP_S * 5);
+
+        let silence = [0f32; HOP_S];
+        for _ in 0..8 {
+            assert_eq!(tracker.do_result(silence.as_ref()).unwrap(), None);
+        }
+        assert_eq!(tracker.get_position(), HOP_S * 8);
+        assert_eq!(tracker.finish(), None);
+        assert!(tracker.do_result([0f32; HOP_S / 2].as_ref()).is_err());
+    }
+
+    #[test]
+    fn test_note_change() {
+        let change = NoteChange::parse(&[64.0, 90.0, 60.0]);
+        assert_eq!(
+            change.off,
+            Some(Note {
+                pitch: 60.0,
+                velocity: 0.0
+            })
+        );
+        assert_eq!(
+            change.on,
+            Some(Note {
+                pitch: 64.0,
+                velocity: 90.0
+            })
+        );
+        assert_eq!(
+            change.iter().collect::<Vec<_>>(),
+            Note::parse(&[64.0, 90.0, 60.0])
+        );
+
+        let change = NoteChange::parse(&[0.0, 0.0, 0.0]);
+        assert!(change.is_empty());
+        assert_eq!(change.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_no_alloc() {
+        const HOP_S: usize = 256;
+
+        let mut notes = Notes::new(HOP_S * 2, HOP_S, 44100).unwrap();
+        let mut tracker = NoteTracker::new(Notes::new(HOP_S * 2, HOP_S, 44100).unwrap());
+
+        let mut input = [0f32; HOP_S];
+        let (_, allocs) = crate::test::count_allocs(|| {
+            for i in 0..64 {
+                for (j, sample) in input.iter_mut().enumerate() {
+                    let t = (i * HOP_S + j) as f32 / 44100.0;
+                    *sample = if i % 16 < 8 {
+                        (t * 440.0 * 2.0 * std::f32::consts::PI).sin()
+                    } else {
+                        0.0
+                    };
+                }
+
+                notes.do_change(input.as_ref()).unwrap();
+                tracker.do_result(input.as_ref()).unwrap();
+            }
+            tracker.finish();
+        });
+
+        assert_eq!(allocs, 0);
+    }
+
+    #[test]
+    fn test_reset() {
+        const HOP_S: usize = 256;
+
+        let sine = |hop: usize| {
+            let mut input = [0f32; HOP_S];
+            for (i, sample) in input.iter_mut().enumerate() {
+                let t = (hop * HOP_S + i) as f32 / 44100.0;
+                *sample = (t * 440.0 * 2.0 * std::f32::consts::PI).sin();
+            }
+            input
+        };
+
+        let mut notes = Notes::new(HOP_S * 2, HOP_S, 44100).unwrap();
+        notes.set_release_drop(20.0);
+
+        for hop in 0..16 {
+            notes.do_result(sine(hop).as_ref()).unwrap();
+        }
+
+        notes.reset().unwrap();
+        assert_eq!(notes.get_release_drop(), 20.0);
+
+        // a fresh detector only sees the first hop
+        let mut fresh = Notes::new(HOP_S * 2, HOP_S, 44100).unwrap();
+        fresh.set_release_drop(20.0);
+
+        assert_eq!(
+            notes.do_change(sine(0).as_ref()).unwrap(),
+            fresh.do_change(sine(0).as_ref()).unwrap()
+        );
+    }
 }
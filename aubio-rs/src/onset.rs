@@ -1,5 +1,5 @@
 use crate::{
-    check_init, ffi,
+    check_buf_hop, check_init, check_sample_rate, ffi,
     vec::{FVec, FVecMut},
     AsNativeStr, Error, Result, SpecMethod, Status,
 };
@@ -136,11 +136,190 @@ impl FromStr for OnsetMode {
             "kl" => Kl,
             "specflux" => SpecFlux,
             "specdiff" => SpecDiff,
-            _ => return Err(Error::InvalidArg),
+            _ => return Err(Error::invalid_arg("OnsetMode", src)),
         })
     }
 }
 
+impl_serde_str!(OnsetMode);
+
+/**
+ * Onset detection configuration
+ *
+ * The optional parameters which are `None` keep the defaults
+ * which _aubio_ selects for the detection method.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnsetConfig {
+    /**
+     * Onset detection type
+     */
+    pub method: OnsetMode,
+
+    /**
+     * Buffer size for phase vocoder
+     */
+    pub buf_size: usize,
+
+    /**
+     * Hop size for phase vocoder
+     */
+    pub hop_size: usize,
+
+    /**
+     * Sampling rate of the input signal
+     */
+    pub sample_rate: u32,
+
+    /**
+     * Peak picking threshold
+     */
+    pub threshold: Option<f32>,
+
+    /**
+     * Silence threshold, in dB
+     */
+    pub silence: Option<f32>,
+
+    /**
+     * Minimum inter onset interval, in samples
+     */
+    pub minioi: Option<usize>,
+
+    /**
+     * Delay, in samples
+     */
+    pub delay: Option<usize>,
+
+    /**
+     * Log compression lambda
+     */
+    pub compression: Option<f32>,
+
+    /**
+     * Adaptive whitening
+     */
+    pub awhitening: Option<bool>,
+}
+
+impl OnsetConfig {
+    /**
+     * Create onset detection configuration with default parameters
+     */
+    pub fn new(method: OnsetMode, buf_size: usize, hop_size: usize, sample_rate: u32) -> Self {
+        Self {
+            method,
+            buf_size,
+            hop_size,
+            sample_rate,
+            threshold: None,
+            silence: None,
+            minioi: None,
+            delay: None,
+            compression: None,
+            awhitening: None,
+        }
+    }
+
+    /**
+     * Create onset detection object using configuration
+     */
+    pub fn build(&self) -> Result<Onset> {
+        let mut onset = Onset::new(self.method, self.buf_size, self.hop_size, self.sample_rate)?;
+
+        if let Some(threshold) = self.threshold {
+            onset.set_threshold(threshold);
+        }
+        if let Some(silence) = self.silence {
+            onset.set_silence(silence);
+        }
+        if let Some(minioi) = self.minioi {
+            onset.set_minioi(minioi);
+        }
+        if let Some(delay) = self.delay {
+            onset.set_delay(delay);
+        }
+        if let Some(compression) = self.compression {
+            onset.set_compression(compression);
+        }
+        if let Some(enable) = self.awhitening {
+            onset.set_awhitening(enable);
+        }
+
+        Ok(onset)
+    }
+}
+
+/**
+ * Detected onset
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnsetEvent {
+    /**
+     * Position of onset, in samples (see `Onset::get_last()`)
+     */
+    pub position: usize,
+
+    /**
+     * Position of onset, in seconds (see `Onset::get_last_s()`)
+     */
+    pub time: f32,
+
+    /**
+     * Value of onset detection function
+     */
+    pub descriptor: f32,
+
+    /**
+     * Value of thresholded onset detection function
+     */
+    pub thresholded_descriptor: f32,
+
+    /**
+     * Index of hop which onset has been detected in
+     */
+    pub hop: usize,
+}
+
+/**
+ * Values of onset detection function for single hop
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OdfFrame {
+    /**
+     * Index of hop
+     */
+    pub hop: usize,
+
+    /**
+     * Position of first sample of hop, in samples
+     */
+    pub position: usize,
+
+    /**
+     * Position of first sample of hop, in seconds
+     */
+    pub time: f32,
+
+    /**
+     * Value of onset detection function
+     */
+    pub descriptor: f32,
+
+    /**
+     * Value of thresholded onset detection function
+     */
+    pub thresholded_descriptor: f32,
+
+    /**
+     * Whether onset has been detected in this hop
+     */
+    pub onset: bool,
+}
+
 /**
  * Onset detection object
  *
@@ -153,6 +332,12 @@ impl FromStr for OnsetMode {
  */
 pub struct Onset {
     onset: *mut ffi::aubio_onset_t,
+    method: OnsetMode,
+    buf_size: usize,
+    hop_size: usize,
+    sample_rate: u32,
+    hops: usize,
+    trace: Option<Vec<OdfFrame>>,
 }
 
 impl Drop for Onset {
@@ -161,6 +346,8 @@ impl Drop for Onset {
     }
 }
 
+unsafe impl Send for Onset {}
+
 impl Onset {
     /**
      * Create onset detection object
@@ -176,18 +363,35 @@ impl Onset {
         hop_size: usize,
         sample_rate: u32,
     ) -> Result<Self> {
-        let onset = unsafe {
+        check_buf_hop(buf_size, hop_size)?;
+        check_sample_rate(sample_rate)?;
+
+        let onset = check_init(|| unsafe {
             ffi::new_aubio_onset(
                 method.as_native_cstr(),
                 buf_size as ffi::uint_t,
                 hop_size as ffi::uint_t,
                 sample_rate as ffi::uint_t,
             )
-        };
-
-        check_init(onset)?;
+        })?;
+
+        Ok(Self {
+            onset,
+            method,
+            buf_size,
+            hop_size,
+            sample_rate,
+            hops: 0,
+            trace: None,
+        })
+    }
 
-        Ok(Self { onset })
+    /**
+     * Enable recording of onset detection function
+     */
+    pub fn with_trace(mut self) -> Self {
+        self.set_trace(true);
+        self
     }
 
     /**
@@ -295,26 +499,133 @@ impl Onset {
         onset.check_size(1)?;
 
         unsafe { ffi::aubio_onset_do(self.onset, input.as_ptr(), onset.as_mut_ptr()) }
+        self.hops += 1;
         Ok(())
     }
 
     /**
      * Execute onset detection
+     *
+     * Returns the onset which has been detected in this frame.
      */
-    pub fn do_result<'i, I>(&mut self, input: I) -> Result<f32>
+    pub fn do_result<'i, I>(&mut self, input: I) -> Result<Option<OnsetEvent>>
+    where
+        I: Into<FVec<'i>>,
+    {
+        let frame = self.do_frame(input)?;
+
+        Ok(if frame.onset {
+            Some(OnsetEvent {
+                position: self.get_last(),
+                time: self.get_last_s(),
+                descriptor: frame.descriptor,
+                thresholded_descriptor: frame.thresholded_descriptor,
+                hop: frame.hop,
+            })
+        } else {
+            None
+        })
+    }
+
+    /**
+     * Execute onset detection
+     *
+     * Returns the values of onset detection function for this frame.
+     * When trace is enabled, the values are also recorded (see `set_trace()`).
+     */
+    pub fn do_frame<'i, I>(&mut self, input: I) -> Result<OdfFrame>
     where
         I: Into<FVec<'i>>,
     {
         let mut onset = [0f32; 1];
         self.do_(input, &mut onset)?;
-        Ok(onset[0])
+
+        let hop = self.hops - 1;
+        let position = hop * self.hop_size;
+        let frame = OdfFrame {
+            hop,
+            position,
+            time: position as f32 / self.sample_rate as f32,
+            descriptor: self.get_descriptor(),
+            thresholded_descriptor: self.get_thresholded_descriptor(),
+            onset: onset[0] != 0.0,
+        };
+
+        if let Some(trace) = &mut self.trace {
+            trace.push(frame);
+        }
+
+        Ok(frame)
+    }
+
+    /**
+     * Get number of processed hops
+     */
+    pub fn get_hops(&self) -> usize {
+        self.hops
+    }
+
+    /**
+     * Enable or disable recording of onset detection function
+     *
+     * When trace is enabled, the frames which are processed by `do_result()` and `do_frame()`
+     * are recorded, so the detection function can be plotted or exported alongside onsets.
+     * Disabling trace drops the recorded frames.
+     */
+    pub fn set_trace(&mut self, enable: bool) {
+        if !enable {
+            self.trace = None;
+        } else if self.trace.is_none() {
+            self.trace = Some(Vec::new());
+        }
+    }
+
+    /**
+     * Get recorded frames of onset detection function
+     */
+    pub fn get_trace(&self) -> &[OdfFrame] {
+        self.trace.as_deref().unwrap_or(&[])
+    }
+
+    /**
+     * Take recorded frames of onset detection function
+     *
+     * The trace remains enabled, but empty.
+     */
+    pub fn take_trace(&mut self) -> Vec<OdfFrame> {
+        self.trace.as_mut().map(std::mem::take).unwrap_or_default()
     }
 
     /**
      * Get hop size
      */
     pub fn get_hop(&self) -> usize {
-        (unsafe { ffi::aubio_pvoc_get_hop(self.onset.cast::<ffi::aubio_pvoc_t>()) }) as usize
+        self.hop_size
+    }
+
+    /**
+     * Get onset detection type
+     */
+    pub fn get_mode(&self) -> OnsetMode {
+        self.method
+    }
+
+    /**
+     * Get current configuration
+     */
+    pub fn config(&self) -> OnsetConfig {
+        OnsetConfig {
+            method: self.method,
+            buf_size: self.buf_size,
+            hop_size: self.hop_size,
+            sample_rate: self.sample_rate,
+            threshold: Some(self.get_threshold()),
+            silence: Some(self.get_silence()),
+            minioi: Some(self.get_minioi()),
+            delay: Some(self.get_delay()),
+            compression: Some(self.get_compression()),
+            awhitening: Some(self.get_awhitening()),
+        }
     }
 
     /**
@@ -523,11 +834,18 @@ impl Onset {
 
     /**
      * Reset onset detection
+     *
+     * The hop counter restarts from zero and the recorded trace is cleared,
+     * but remains enabled.
      */
     pub fn reset(&mut self) {
         unsafe {
             ffi::aubio_onset_reset(self.onset);
         }
+        self.hops = 0;
+        if let Some(trace) = &mut self.trace {
+            trace.clear();
+        }
     }
 }
 
@@ -540,23 +858,134 @@ mod test {
         // TODO
     }
 
+    #[test]
+    fn test_config() {
+        let mut config = OnsetConfig::new(OnsetMode::SpecFlux, 1024, 256, 44100);
+        config.threshold = Some(0.5);
+        config.minioi = Some(1024);
+        config.awhitening = Some(true);
+
+        let onset = config.build().unwrap();
+
+        assert_eq!(onset.get_mode(), OnsetMode::SpecFlux);
+        assert_eq!(onset.get_hop(), 256);
+        assert_eq!(onset.get_threshold(), 0.5);
+        assert_eq!(onset.get_minioi(), 1024);
+        assert!(onset.get_awhitening());
+
+        let config = onset.config();
+        assert_eq!(config.build().unwrap().config(), config);
+    }
+
     #[test]
     fn test_wrong_params() {
         const WIN_S: usize = 1024;
         const HOP_S: usize = WIN_S / 2;
         const SAMPLERATE: u32 = 44100;
+        let invalid = |name, value: &str| {
+            Some(Error::InvalidArg {
+                name,
+                value: value.into(),
+            })
+        };
         // HOP_S < 1
-        assert!(Onset::new(Default::default(), 5, 0, SAMPLERATE).is_err());
+        assert_eq!(
+            Onset::new(Default::default(), 5, 0, SAMPLERATE).err(),
+            invalid("hop_size", "0")
+        );
         // buf_size < 2
-        assert!(Onset::new(Default::default(), 1, 1, SAMPLERATE).is_err());
+        assert_eq!(
+            Onset::new(Default::default(), 1, 1, SAMPLERATE).err(),
+            invalid("buf_size", "1")
+        );
         // buf_size < HOP_S
-        assert!(Onset::new(Default::default(), HOP_S, WIN_S, SAMPLERATE).is_err());
+        assert_eq!(
+            Onset::new(Default::default(), HOP_S, WIN_S, SAMPLERATE).err(),
+            invalid("hop_size", "1024")
+        );
         // SAMPLERATE < 1
-        assert!(Onset::new(Default::default(), 1024, 512, 0).is_err());
+        assert_eq!(
+            Onset::new(Default::default(), 1024, 512, 0).err(),
+            invalid("sample_rate", "0")
+        );
         // pv creation might fail
         assert_eq!(
             Onset::new(Default::default(), 5, 2, SAMPLERATE).is_ok(),
             cfg!(any(feature = "with-fftw3", feature = "with-fftw3f"))
         );
     }
+
+    #[test]
+    fn test_events() {
+        const HOP_S: usize = 256;
+
+        let mut onset = Onset::new(OnsetMode::Energy, HOP_S * 4, HOP_S, 44100)
+            .unwrap()
+            .with_trace();
+        let mut events = Vec::new();
+
+        for hop in 0..64 {
+            let mut input = [0f32; HOP_S];
+            if hop % 16 == 8 {
+                for (i, sample) in input.iter_mut().enumerate() {
+                    *sample = if i % 2 == 0 { 0.5 } else { -0.5 };
+                }
+            }
+            if let Some(event) = onset.do_result(input.as_ref()).unwrap() {
+                events.push(event);
+            }
+        }
+
+        assert_eq!(onset.get_hops(), 64);
+        assert_eq!(onset.get_trace().len(), 64);
+
+        let trace = onset.take_trace();
+        assert!(onset.get_trace().is_empty());
+
+        for (hop, frame) in trace.iter().enumerate() {
+            assert_eq!(frame.hop, hop);
+            assert_eq!(frame.position, hop * HOP_S);
+        }
+
+        assert!(!events.is_empty());
+        assert_eq!(
+            events.len(),
+            trace.iter().filter(|frame| frame.onset).count()
+        );
+
+        for event in &events {
+            let frame = &trace[event.hop];
+            assert!(frame.onset);
+            assert_eq!(event.descriptor, frame.descriptor);
+            assert_eq!(event.thresholded_descriptor, frame.thresholded_descriptor);
+            assert!(event.position <= frame.position + HOP_S);
+        }
+
+        onset.set_trace(false);
+        onset.do_frame([0f32; HOP_S].as_ref()).unwrap();
+        assert!(onset.get_trace().is_empty());
+        assert_eq!(onset.get_hops(), 65);
+    }
+
+    #[test]
+    fn test_reset() {
+        const HOP_S: usize = 256;
+
+        let mut onset = Onset::new(OnsetMode::Energy, HOP_S * 4, HOP_S, 44100)
+            .unwrap()
+            .with_trace();
+
+        onset.do_frame([0f32; HOP_S].as_ref()).unwrap();
+        onset.do_frame([0f32; HOP_S].as_ref()).unwrap();
+        assert_eq!(onset.get_trace().len(), 2);
+
+        onset.reset();
+        assert_eq!(onset.get_hops(), 0);
+        assert!(onset.get_trace().is_empty());
+
+        let frame = onset.do_frame([0f32; HOP_S].as_ref()).unwrap();
+        assert_eq!(frame.hop, 0);
+        assert_eq!(frame.position, 0);
+        assert_eq!(onset.get_trace(), &[frame]);
+    }
 }
@@ -0,0 +1,268 @@
+use crate::{check_init, ffi, Error, Result, Status};
+
+/**
+ * Parameter with linear interpolation
+ *
+ * This object manages a parameter, with minimum and maximum values,
+ * and a number of steps to compute linear interpolation between two values.
+ *
+ * Setting a new target value does not change the current value immediately.
+ * Instead, each call to `Parameter::next_value` moves the current value
+ * one step closer to the target, which avoids clicks and zipper noise
+ * when the parameter controls gains or frequencies.
+ */
+pub struct Parameter {
+    param: *mut ffi::aubio_parameter_t,
+}
+
+impl Drop for Parameter {
+    fn drop(&mut self) {
+        unsafe { ffi::del_aubio_parameter(self.param) }
+    }
+}
+
+unsafe impl Send for Parameter {}
+
+impl Parameter {
+    /**
+     * Create new parameter object
+     *
+     * - `min_value` Minimum value of the new parameter
+     * - `max_value` Maximum value of the new parameter
+     * - `steps` Number of steps to interpolate from the old value to the target value
+     *
+     * The current value is initialized to `min_value`.
+     */
+    pub fn new(min_value: f32, max_value: f32, steps: usize) -> Result<Self> {
+        let param = check_init(|| unsafe {
+            ffi::new_aubio_parameter(min_value, max_value, steps as ffi::uint_t)
+        })?;
+
+        Ok(Self { param })
+    }
+
+    /**
+     * Set minimum value
+     */
+    pub fn with_min(mut self, min_value: f32) -> Result<Self> {
+        self.set_min(min_value).map(|_| self)
+    }
+
+    /**
+     * Set maximum value
+     */
+    pub fn with_max(mut self, max_value: f32) -> Result<Self> {
+        self.set_max(max_value).map(|_| self)
+    }
+
+    /**
+     * Set number of steps used for interpolation
+     */
+    pub fn with_steps(mut self, steps: usize) -> Result<Self> {
+        self.set_steps(steps).map(|_| self)
+    }
+
+    /**
+     * Set current value, skipping interpolation
+     */
+    pub fn with_current(mut self, value: f32) -> Result<Self> {
+        self.set_current(value).map(|_| self)
+    }
+
+    /**
+     * Set target value
+     *
+     * Values outside of the `[min, max]` range are clamped, and an error is returned.
+     */
+    pub fn set_target(&mut self, value: f32) -> Status {
+        if 0 == unsafe { ffi::aubio_parameter_set_target_value(self.param, value) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("value", value))
+        }
+    }
+
+    /**
+     * Get next interpolated value
+     *
+     * Once the target is reached, the target value is returned.
+     */
+    pub fn next_value(&mut self) -> f32 {
+        unsafe { ffi::aubio_parameter_get_next_value(self.param) }
+    }
+
+    /**
+     * Get current value, without interpolation
+     */
+    pub fn current(&self) -> f32 {
+        unsafe { ffi::aubio_parameter_get_current_value(self.param) }
+    }
+
+    /**
+     * Set current value, skipping interpolation
+     */
+    pub fn set_current(&mut self, value: f32) -> Status {
+        if 0 == unsafe { ffi::aubio_parameter_set_current_value(self.param, value) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("value", value))
+        }
+    }
+
+    /**
+     * Iterate over interpolated values until the target is reached
+     *
+     * The last value yielded is the target value.
+     */
+    pub fn ramp(&mut self) -> Ramp<'_> {
+        Ramp { param: self }
+    }
+
+    /**
+     * Set number of steps used for interpolation
+     */
+    pub fn set_steps(&mut self, steps: usize) -> Status {
+        if 0 == unsafe { ffi::aubio_parameter_set_steps(self.param, steps as ffi::uint_t) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("steps", steps))
+        }
+    }
+
+    /**
+     * Get number of steps used for interpolation
+     */
+    pub fn get_steps(&self) -> usize {
+        (unsafe { ffi::aubio_parameter_get_steps(self.param) }) as usize
+    }
+
+    /**
+     * Set minimum value
+     */
+    pub fn set_min(&mut self, min_value: f32) -> Status {
+        if 0 == unsafe { ffi::aubio_parameter_set_min_value(self.param, min_value) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("min_value", min_value))
+        }
+    }
+
+    /**
+     * Get minimum value
+     */
+    pub fn get_min(&self) -> f32 {
+        unsafe { ffi::aubio_parameter_get_min_value(self.param) }
+    }
+
+    /**
+     * Set maximum value
+     */
+    pub fn set_max(&mut self, max_value: f32) -> Status {
+        if 0 == unsafe { ffi::aubio_parameter_set_max_value(self.param, max_value) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("max_value", max_value))
+        }
+    }
+
+    /**
+     * Get maximum value
+     */
+    pub fn get_max(&self) -> f32 {
+        unsafe { ffi::aubio_parameter_get_max_value(self.param) }
+    }
+}
+
+/**
+ * Iterator over interpolated values of a parameter
+ *
+ * Created by `Parameter::ramp`.
+ */
+pub struct Ramp<'a> {
+    param: &'a mut Parameter,
+}
+
+impl<'a> Iterator for Ramp<'a> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.param.current();
+        let next = self.param.next_value();
+
+        if next == current {
+            None
+        } else {
+            Some(next)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test() {
+        let mut param = Parameter::new(0.0, 1.0, 4).unwrap();
+
+        assert_eq!(param.get_min(), 0.0);
+        assert_eq!(param.get_max(), 1.0);
+        assert_eq!(param.get_steps(), 4);
+        assert_eq!(param.current(), 0.0);
+
+        param.set_target(1.0).unwrap();
+        assert_eq!(param.current(), 0.0);
+
+        let ramp: Vec<f32> = param.ramp().collect();
+        assert_eq!(ramp, [0.25, 0.5, 0.75, 1.0]);
+        assert_eq!(param.next_value(), 1.0);
+        assert_eq!(param.ramp().count(), 0);
+
+        param.set_target(0.5).unwrap();
+        assert_eq!(param.next_value(), 0.875);
+        param.set_current(0.5).unwrap();
+        assert_eq!(param.current(), 0.5);
+    }
+
+    #[test]
+    fn test_builder() {
+        let mut param = Parameter::new(0.0, 1.0, 10)
+            .unwrap()
+            .with_min(-1.0)
+            .unwrap()
+            .with_max(2.0)
+            .unwrap()
+            .with_steps(2)
+            .unwrap()
+            .with_current(2.0)
+            .unwrap();
+
+        assert_eq!(param.get_min(), -1.0);
+        assert_eq!(param.get_max(), 2.0);
+        assert_eq!(param.get_steps(), 2);
+
+        param.set_target(-1.0).unwrap();
+        let ramp: Vec<f32> = param.ramp().collect();
+        assert_eq!(ramp, [0.5, -1.0]);
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        let mut param = Parameter::new(0.0, 1.0, 4).unwrap();
+
+        assert_eq!(
+            param.set_target(2.0),
+            Err(Error::InvalidArg {
+                name: "value",
+                value: "2".into()
+            })
+        );
+        assert_eq!(
+            param.set_steps(0),
+            Err(Error::InvalidArg {
+                name: "steps",
+                value: "0".into()
+            })
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    check_init, ffi,
+    check_arg, check_fft_size, check_init, check_sample_rate, ffi,
     vec::{FVec, FVecMut},
     AsNativeStr, Error, Result, Status,
 };
@@ -83,6 +83,17 @@ impl Default for PitchMode {
     }
 }
 
+impl PitchMode {
+    /**
+     * Whether the algorithm computes FFT of the input buffer
+     */
+    fn uses_fft(self) -> bool {
+        use self::PitchMode::*;
+
+        !matches!(self, Schmitt | Yin)
+    }
+}
+
 impl AsNativeStr for PitchMode {
     fn as_native_str(&self) -> &'static str {
         use self::PitchMode::*;
@@ -125,7 +136,7 @@ impl FromStr for PitchMode {
             "yinfast" => Yinfast,
             "yinfft" => Yinfft,
             "specacf" => Specacf,
-            _ => return Err(Error::InvalidArg),
+            _ => return Err(Error::invalid_arg("PitchMode", src)),
         })
     }
 }
@@ -198,17 +209,103 @@ impl FromStr for PitchUnit {
             "midi" => Midi,
             "cent" => Cent,
             "bin" => Bin,
-            _ => return Err(Error::InvalidArg),
+            _ => return Err(Error::invalid_arg("PitchUnit", src)),
         })
     }
 }
 
+impl_serde_str!(PitchMode, PitchUnit);
+
+/**
+ * Pitch detection configuration
+ *
+ * The optional parameters which are `None` keep the defaults
+ * which _aubio_ selects for the detection method.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PitchConfig {
+    /**
+     * Pitch detection algorithm
+     */
+    pub method: PitchMode,
+
+    /**
+     * Size of the input buffer to analyse
+     */
+    pub buf_size: usize,
+
+    /**
+     * Step size between two consecutive analysis instant
+     */
+    pub hop_size: usize,
+
+    /**
+     * Sampling rate of the signal
+     */
+    pub sample_rate: u32,
+
+    /**
+     * Yin or yinfft tolerance threshold
+     */
+    pub tolerance: Option<f32>,
+
+    /**
+     * Silence threshold, in dB
+     */
+    pub silence: Option<f32>,
+
+    /**
+     * Output unit
+     */
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unit: PitchUnit,
+}
+
+impl PitchConfig {
+    /**
+     * Create pitch detection configuration with default parameters
+     */
+    pub fn new(method: PitchMode, buf_size: usize, hop_size: usize, sample_rate: u32) -> Self {
+        Self {
+            method,
+            buf_size,
+            hop_size,
+            sample_rate,
+            tolerance: None,
+            silence: None,
+            unit: PitchUnit::default(),
+        }
+    }
+
+    /**
+     * Create pitch detection object using configuration
+     */
+    pub fn build(&self) -> Result<Pitch> {
+        let mut pitch = Pitch::new(self.method, self.buf_size, self.hop_size, self.sample_rate)?;
+
+        if let Some(tolerance) = self.tolerance {
+            pitch.set_tolerance(tolerance);
+        }
+        if let Some(silence) = self.silence {
+            pitch.set_silence(silence);
+        }
+        pitch.set_unit(self.unit);
+
+        Ok(pitch)
+    }
+}
+
 /**
  * Pitch detection object
  */
 pub struct Pitch {
     pitch: *mut ffi::aubio_pitch_t,
+    method: PitchMode,
+    buf_size: usize,
     hop_size: usize,
+    sample_rate: u32,
+    unit: PitchUnit,
 }
 
 impl Drop for Pitch {
@@ -217,6 +314,8 @@ impl Drop for Pitch {
     }
 }
 
+unsafe impl Send for Pitch {}
+
 impl Pitch {
     /**
      * Creation of the pitch detection object
@@ -232,18 +331,32 @@ impl Pitch {
         hop_size: usize,
         sample_rate: u32,
     ) -> Result<Self> {
-        let pitch = unsafe {
+        check_arg("hop_size", hop_size, hop_size >= 1)?;
+        if method.uses_fft() {
+            check_fft_size("buf_size", buf_size)?;
+        } else {
+            check_arg("buf_size", buf_size, buf_size >= 1)?;
+        }
+        check_arg("hop_size", hop_size, hop_size <= buf_size)?;
+        check_sample_rate(sample_rate)?;
+
+        let pitch = check_init(|| unsafe {
             ffi::new_aubio_pitch(
                 method.as_native_cstr(),
                 buf_size as ffi::uint_t,
                 hop_size as ffi::uint_t,
                 sample_rate as ffi::uint_t,
             )
-        };
-
-        check_init(pitch)?;
-
-        Ok(Self { pitch, hop_size })
+        })?;
+
+        Ok(Self {
+            pitch,
+            method,
+            buf_size,
+            hop_size,
+            sample_rate,
+            unit: PitchUnit::default(),
+        })
     }
 
     /**
@@ -277,6 +390,28 @@ impl Pitch {
         self.hop_size
     }
 
+    /**
+     * Get pitch detection algorithm
+     */
+    pub fn get_mode(&self) -> PitchMode {
+        self.method
+    }
+
+    /**
+     * Get current configuration
+     */
+    pub fn config(&self) -> PitchConfig {
+        PitchConfig {
+            method: self.method,
+            buf_size: self.buf_size,
+            hop_size: self.hop_size,
+            sample_rate: self.sample_rate,
+            tolerance: Some(self.get_tolerance()),
+            silence: Some(self.get_silence()),
+            unit: self.unit,
+        }
+    }
+
     /**
      * Execute pitch detection on an input signal frame
      *
@@ -353,6 +488,14 @@ impl Pitch {
         unsafe {
             ffi::aubio_pitch_set_unit(self.pitch, unit.as_native_cstr());
         }
+        self.unit = unit;
+    }
+
+    /**
+     * Get the output unit of the pitch detection object
+     */
+    pub fn get_unit(&self) -> PitchUnit {
+        self.unit
     }
 
     /**
@@ -361,4 +504,112 @@ impl Pitch {
     pub fn get_confidence(&self) -> f32 {
         unsafe { ffi::aubio_pitch_get_confidence(self.pitch) }
     }
+
+    /**
+     * Reset pitch detection
+     *
+     * The internal buffers are cleared by recreating the detection object
+     * with the same method, sizes, tolerance, silence threshold and unit.
+     */
+    pub fn reset(&mut self) -> Status {
+        *self = self.config().build()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test_config() {
+        let mut config = PitchConfig::new(PitchMode::Yin, 2048, 512, 44100);
+        config.tolerance = Some(0.2);
+        config.unit = PitchUnit::Midi;
+
+        let pitch = config.build().unwrap();
+
+        assert_eq!(pitch.get_mode(), PitchMode::Yin);
+        assert_eq!(pitch.get_unit(), PitchUnit::Midi);
+        assert_eq!(pitch.get_tolerance(), 0.2);
+
+        let config = pitch.config();
+        assert_eq!(config.build().unwrap().config(), config);
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        let invalid = |name, value: &str| {
+            Some(Error::InvalidArg {
+                name,
+                value: value.into(),
+            })
+        };
+
+        assert_eq!(
+            Pitch::new(PitchMode::Yinfft, 1024, 0, 44100).err(),
+            invalid("hop_size", "0")
+        );
+        assert_eq!(
+            Pitch::new(PitchMode::Yin, 0, 1, 44100).err(),
+            invalid("buf_size", "0")
+        );
+        assert_eq!(
+            Pitch::new(PitchMode::Yinfft, 1, 1, 44100).err(),
+            invalid("buf_size", "1")
+        );
+        assert_eq!(
+            Pitch::new(PitchMode::Yin, 512, 1024, 44100).err(),
+            invalid("hop_size", "1024")
+        );
+        assert_eq!(
+            Pitch::new(PitchMode::Yin, 1024, 512, 0).err(),
+            invalid("sample_rate", "0")
+        );
+        // yin does not compute FFT, so any buffer size is accepted
+        assert!(Pitch::new(PitchMode::Yin, 1000, 500, 44100).is_ok());
+        if !cfg!(feature = "with-fftw3") {
+            assert_eq!(
+                Pitch::new(PitchMode::Yinfft, 1000, 500, 44100).err(),
+                invalid("buf_size", "1000")
+            );
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        const BUF_S: usize = 1024;
+        const HOP_S: usize = 256;
+
+        let sine = |hop: usize| {
+            let mut input = [0f32; HOP_S];
+            for (i, sample) in input.iter_mut().enumerate() {
+                let t = (hop * HOP_S + i) as f32 / 44100.0;
+                *sample = (t * 440.0 * 2.0 * std::f32::consts::PI).sin();
+            }
+            input
+        };
+
+        let mut pitch = Pitch::new(PitchMode::Yin, BUF_S, HOP_S, 44100).unwrap();
+        pitch.set_unit(PitchUnit::Midi);
+        pitch.set_tolerance(0.2);
+
+        for hop in 0..8 {
+            pitch.do_result(sine(hop).as_ref()).unwrap();
+        }
+
+        pitch.reset().unwrap();
+        assert_eq!(pitch.get_unit(), PitchUnit::Midi);
+        assert_eq!(pitch.get_tolerance(), 0.2);
+
+        // a fresh detector only sees the first hop
+        let mut fresh = Pitch::new(PitchMode::Yin, BUF_S, HOP_S, 44100).unwrap();
+        fresh.set_unit(PitchUnit::Midi);
+        fresh.set_tolerance(0.2);
+
+        assert_eq!(
+            pitch.do_result(sine(0).as_ref()).unwrap(),
+            fresh.do_result(sine(0).as_ref()).unwrap()
+        );
+    }
 }
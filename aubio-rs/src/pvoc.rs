@@ -1,5 +1,5 @@
 use crate::{
-    check_init, ffi,
+    check_arg, check_fft_size, check_init, ffi,
     vec::{CVec, CVecMut, FVec, FVecMut},
     AsNativeStr, Error, Result, Status, WindowType,
 };
@@ -9,6 +9,7 @@ use crate::{
  */
 pub struct PVoc {
     pvoc: *mut ffi::aubio_pvoc_t,
+    window: Option<WindowType>,
 }
 
 impl Drop for PVoc {
@@ -19,6 +20,8 @@ impl Drop for PVoc {
     }
 }
 
+unsafe impl Send for PVoc {}
+
 impl PVoc {
     /**
      * Create phase vocoder object
@@ -27,11 +30,15 @@ impl PVoc {
      * - `hop_size` Step size between two consecutive analysis
      */
     pub fn new(win_size: usize, hop_size: usize) -> Result<Self> {
-        let pvoc = unsafe { ffi::new_aubio_pvoc(win_size as ffi::uint_t, hop_size as ffi::uint_t) };
+        check_arg("hop_size", hop_size, hop_size >= 1)?;
+        check_fft_size("win_size", win_size)?;
+        check_arg("hop_size", hop_size, hop_size <= win_size)?;
 
-        check_init(pvoc)?;
+        let pvoc = check_init(|| unsafe {
+            ffi::new_aubio_pvoc(win_size as ffi::uint_t, hop_size as ffi::uint_t)
+        })?;
 
-        Ok(Self { pvoc })
+        Ok(Self { pvoc, window: None })
     }
 
     /**
@@ -116,11 +123,29 @@ impl PVoc {
      */
     pub fn set_window(&mut self, window_type: WindowType) -> Status {
         if 0 == unsafe { ffi::aubio_pvoc_set_window(self.pvoc, window_type.as_native_cstr()) } {
+            self.window = Some(window_type);
             Ok(())
         } else {
-            Err(Error::InvalidArg)
+            Err(Error::invalid_arg("window_type", window_type))
         }
     }
+
+    /**
+     * Reset phase vocoder
+     *
+     * The analysis and overlap-add buffers are cleared by recreating the phase vocoder
+     * with the same window size, hop size and window type.
+     */
+    pub fn reset(&mut self) -> Status {
+        let mut pvoc = Self::new(self.get_win(), self.get_hop())?;
+
+        if let Some(window_type) = self.window {
+            pvoc.set_window(window_type)?;
+        }
+
+        *self = pvoc;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -160,4 +185,56 @@ mod test {
             println!("out: {:?}", out);
         }
     }
+
+    #[test]
+    fn test_reset() {
+        const WIN_S: usize = 32;
+        const HOP_S: usize = WIN_S / 4;
+        let in_ = [1f32; HOP_S];
+        let mut fftgrain = carr!(WIN_S);
+        let mut out = farr!(HOP_S);
+
+        let mut pv = PVoc::new(WIN_S, HOP_S)
+            .unwrap()
+            .with_window(WindowType::Hamming)
+            .unwrap();
+
+        for _i in 0..4 {
+            pv.do_(in_.as_ref(), fftgrain.as_mut()).unwrap();
+            pv.rdo(fftgrain.as_ref(), out.as_mut()).unwrap();
+        }
+
+        pv.reset().unwrap();
+        assert_eq!(pv.get_win(), WIN_S);
+        assert_eq!(pv.get_hop(), HOP_S);
+
+        // a fresh phase vocoder only sees the first hop
+        let mut fresh = PVoc::new(WIN_S, HOP_S)
+            .unwrap()
+            .with_window(WindowType::Hamming)
+            .unwrap();
+        let mut expected = carr!(WIN_S);
+
+        pv.do_(in_.as_ref(), fftgrain.as_mut()).unwrap();
+        fresh.do_(in_.as_ref(), expected.as_mut()).unwrap();
+
+        assert_eq!(fftgrain.as_ref(), expected.as_ref());
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        let invalid = |name, value: &str| {
+            Some(Error::InvalidArg {
+                name,
+                value: value.into(),
+            })
+        };
+
+        assert_eq!(PVoc::new(32, 0).err(), invalid("hop_size", "0"));
+        assert_eq!(PVoc::new(1, 1).err(), invalid("win_size", "1"));
+        assert_eq!(PVoc::new(16, 32).err(), invalid("hop_size", "32"));
+        if !cfg!(feature = "with-fftw3") {
+            assert_eq!(PVoc::new(30, 10).err(), invalid("win_size", "30"));
+        }
+    }
 }
@@ -60,17 +60,20 @@ impl FromStr for ResampleMode {
             "fastest" => Fastest,
             "order_hold" => OrderHold,
             "linear" => Linear,
-            _ => return Err(Error::InvalidArg),
+            _ => return Err(Error::invalid_arg("ResampleMode", src)),
         })
     }
 }
 
+impl_serde_str!(ResampleMode);
+
 /**
  * Resampler object
  */
 pub struct Resampler {
     resampler: *mut ffi::aubio_resampler_t,
     ratio: f32,
+    mode: ResampleMode,
 }
 
 impl Drop for Resampler {
@@ -79,6 +82,8 @@ impl Drop for Resampler {
     }
 }
 
+unsafe impl Send for Resampler {}
+
 impl Resampler {
     /**
      * Create resampler object
@@ -87,11 +92,14 @@ impl Resampler {
      * - `type` Resampling method
      */
     pub fn new(ratio: f32, mode: ResampleMode) -> Result<Self> {
-        let resampler = unsafe { ffi::new_aubio_resampler(ratio, mode as ffi::uint_t) };
-
-        check_init(resampler)?;
+        let resampler =
+            check_init(|| unsafe { ffi::new_aubio_resampler(ratio, mode as ffi::uint_t) })?;
 
-        Ok(Self { resampler, ratio })
+        Ok(Self {
+            resampler,
+            ratio,
+            mode,
+        })
     }
 
     /**
@@ -120,4 +128,58 @@ impl Resampler {
         unsafe { ffi::aubio_resampler_do(self.resampler, input.as_ptr(), output.as_mut_ptr()) }
         Ok(())
     }
+
+    /**
+     * Reset resampler
+     *
+     * The interpolation history is cleared by recreating the resampler
+     * with the same ratio and method.
+     */
+    pub fn reset(&mut self) -> Status {
+        *self = Self::new(self.ratio, self.mode)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test_reset() {
+        const HOP_S: usize = 256;
+
+        let sine = |hop: usize| {
+            let mut input = [0f32; HOP_S];
+            for (i, sample) in input.iter_mut().enumerate() {
+                let t = (hop * HOP_S + i) as f32 / 44100.0;
+                *sample = (t * 440.0 * 2.0 * std::f32::consts::PI).sin();
+            }
+            input
+        };
+
+        // resampler is not available without libsamplerate
+        let mut resampler = match Resampler::new(0.5, ResampleMode::Linear) {
+            Ok(resampler) => resampler,
+            Err(_) => return,
+        };
+        let mut output = [0f32; HOP_S / 2];
+
+        for hop in 0..4 {
+            resampler.do_(sine(hop).as_ref(), output.as_mut()).unwrap();
+        }
+
+        resampler.reset().unwrap();
+        assert_eq!(resampler.get_ratio(), 0.5);
+        assert_eq!(resampler.mode, ResampleMode::Linear);
+
+        // a fresh resampler has no interpolation history
+        let mut fresh = Resampler::new(0.5, ResampleMode::Linear).unwrap();
+        let mut expected = [0f32; HOP_S / 2];
+
+        resampler.do_(sine(0).as_ref(), output.as_mut()).unwrap();
+        fresh.do_(sine(0).as_ref(), expected.as_mut()).unwrap();
+
+        assert_eq!(output, expected);
+    }
 }
@@ -0,0 +1,359 @@
+use crate::{
+    check_init, ffi, path_to_cstring,
+    vec::{FMat, FMatMut, FMatVecs, FMatVecsMut, FVec, FVecMut},
+    Error, Result, Status,
+};
+
+use std::path::Path;
+
+/**
+ * Load and play sound files
+ *
+ * This object reads a media file in blocks of `hop_size` frames
+ * and adds them to the output while playing.
+ */
+pub struct Sampler {
+    sampler: *mut ffi::aubio_sampler_t,
+    hop_size: usize,
+    loaded: bool,
+}
+
+impl Drop for Sampler {
+    fn drop(&mut self) {
+        unsafe { ffi::del_aubio_sampler(self.sampler) }
+    }
+}
+
+// The sampler owns its source, see `Source`
+unsafe impl Send for Sampler {}
+
+impl Sampler {
+    /**
+     * Maximum number of channels supported by `Sampler::do_multi` and `Sampler::mix_multi`
+     */
+    pub const MAX_CHANNELS: usize = 4;
+
+    /**
+     * Create new sampler object
+     *
+     * - `sample_rate` Sampling rate of the new sampler
+     * - `hop_size` Block size of the new sampler
+     */
+    pub fn new(sample_rate: u32, hop_size: usize) -> Result<Self> {
+        let sampler = check_init(|| unsafe {
+            ffi::new_aubio_sampler(sample_rate as ffi::uint_t, hop_size as ffi::uint_t)
+        })?;
+
+        Ok(Self {
+            sampler,
+            hop_size,
+            loaded: false,
+        })
+    }
+
+    /**
+     * Load source in sampler
+     */
+    pub fn with_source<P: AsRef<Path>>(mut self, path: P) -> Result<Self> {
+        self.load(path).map(|_| self)
+    }
+
+    /**
+     * Get hop size
+     */
+    pub fn get_hop(&self) -> usize {
+        self.hop_size
+    }
+
+    /**
+     * Load source in sampler
+     *
+     * - `path` The file path or URI of the source to load
+     */
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> Status {
+        let path = path_to_cstring(path.as_ref())?;
+
+        // aubio drops the previous source even when loading fails
+        self.loaded = false;
+
+        if 0 == unsafe { ffi::aubio_sampler_load(self.sampler, path.as_ptr()) } {
+            self.loaded = true;
+            Ok(())
+        } else {
+            Err(Error::FailedIo)
+        }
+    }
+
+    /**
+     * Process sampler
+     *
+     * - `input` Input samples to be added to the output (at least `hop_size` long)
+     * - `output` Output samples (`hop_size` long)
+     *
+     * The samples from the playing source and from the input are added to the output.
+     */
+    pub fn do_<'i, 'o, I, O>(&mut self, input: I, output: O) -> Status
+    where
+        I: Into<FVec<'i>>,
+        O: Into<FVecMut<'o>>,
+    {
+        let input = input.into();
+        let mut output = output.into();
+
+        if output.size() != self.get_hop() {
+            return Err(Error::mismatch_size(self.get_hop(), output.size()));
+        }
+        if input.size() < output.size() {
+            return Err(Error::mismatch_size(output.size(), input.size()));
+        }
+
+        unsafe { ffi::aubio_sampler_do(self.sampler, input.as_ptr(), output.as_mut_ptr()) }
+        Ok(())
+    }
+
+    /**
+     * Mix samples from the playing source into output
+     *
+     * - `output` Samples to add to (`hop_size` long)
+     */
+    pub fn mix<'o, O>(&mut self, output: O) -> Status
+    where
+        O: Into<FVecMut<'o>>,
+    {
+        let mut output = output.into();
+
+        if output.size() != self.get_hop() {
+            return Err(Error::mismatch_size(self.get_hop(), output.size()));
+        }
+
+        unsafe { ffi::aubio_sampler_do(self.sampler, std::ptr::null(), output.as_mut_ptr()) }
+        Ok(())
+    }
+
+    /**
+     * Mix multiple channels of samples from the playing source into output
+     *
+     * - `output` Samples to add to (up to `MAX_CHANNELS` high and `hop_size` long)
+     */
+    pub fn mix_multi<'o, O>(&mut self, output: O) -> Status
+    where
+        O: Into<FMatMut<'o, FMatVecsMut>>,
+    {
+        let mut output = output.into();
+
+        self.check_multi_output(&output)?;
+
+        unsafe { ffi::aubio_sampler_do_multi(self.sampler, std::ptr::null(), output.as_mut_ptr()) }
+        Ok(())
+    }
+
+    /**
+     * Process sampler, multiple channels
+     *
+     * - `input` Input samples to be added to the output (same dimensions as output or bigger)
+     * - `output` Output samples (up to `MAX_CHANNELS` high and `hop_size` long)
+     *
+     * The samples from the playing source and from the input are added to the output.
+     */
+    pub fn do_multi<'i, 'o, I, O>(&mut self, input: I, output: O) -> Status
+    where
+        I: Into<FMat<'i, FMatVecs>>,
+        O: Into<FMatMut<'o, FMatVecsMut>>,
+    {
+        let input = input.into();
+        let mut output = output.into();
+
+        self.check_multi_output(&output)?;
+
+        if input.length() < output.length() {
+            return Err(Error::mismatch_size(output.length(), input.length()));
+        }
+        if input.height() < output.height() {
+            return Err(Error::mismatch_size(output.height(), input.height()));
+        }
+
+        unsafe { ffi::aubio_sampler_do_multi(self.sampler, input.as_ptr(), output.as_mut_ptr()) }
+        Ok(())
+    }
+
+    fn check_multi_output(&self, output: &FMatMut<FMatVecsMut>) -> Status {
+        if output.length() != self.get_hop() {
+            Err(Error::mismatch_size(self.get_hop(), output.length()))
+        } else if output.height() > Self::MAX_CHANNELS {
+            Err(Error::mismatch_size(Self::MAX_CHANNELS, output.height()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /**
+     * Get current playing state
+     */
+    pub fn get_playing(&self) -> bool {
+        0 != unsafe { ffi::aubio_sampler_get_playing(self.sampler) }
+    }
+
+    /**
+     * Set current playing state
+     *
+     * A source should be loaded before playing.
+     */
+    pub fn set_playing(&mut self, playing: bool) -> Status {
+        if playing && !self.loaded {
+            return Err(Error::invalid_arg("source", "not loaded"));
+        }
+
+        if 0 == unsafe { ffi::aubio_sampler_set_playing(self.sampler, playing as ffi::uint_t) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("playing", playing))
+        }
+    }
+
+    /**
+     * Play sample from start
+     *
+     * A source should be loaded before playing.
+     */
+    pub fn play(&mut self) -> Status {
+        if !self.loaded {
+            return Err(Error::invalid_arg("source", "not loaded"));
+        }
+
+        if 0 == unsafe { ffi::aubio_sampler_play(self.sampler) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("playing", true))
+        }
+    }
+
+    /**
+     * Stop sample
+     */
+    pub fn stop(&mut self) -> Status {
+        if 0 == unsafe { ffi::aubio_sampler_stop(self.sampler) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("playing", false))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use std::env::temp_dir;
+
+    const SAMPLERATE: u32 = 44100;
+    const HOP_S: usize = 256;
+
+    #[test]
+    fn test() {
+        let path = temp_dir().join("aubio-rs-sampler.wav");
+        let block = [0.5f32; HOP_S];
+
+        let mut sink = Sink::new(&path, SAMPLERATE).unwrap();
+        sink.do_(block.as_ref(), HOP_S).unwrap();
+        sink.do_(block.as_ref(), HOP_S / 2).unwrap();
+        sink.close().unwrap();
+
+        let mut sampler = Sampler::new(SAMPLERATE, HOP_S)
+            .unwrap()
+            .with_source(&path)
+            .unwrap();
+
+        assert_eq!(sampler.get_hop(), HOP_S);
+        assert!(!sampler.get_playing());
+
+        let input = [0.25f32; HOP_S];
+        let mut output = [0f32; HOP_S];
+
+        sampler.do_(input.as_ref(), output.as_mut()).unwrap();
+        assert!(output.iter().all(|s| (s - 0.25).abs() < 1e-6));
+
+        sampler.play().unwrap();
+        assert!(sampler.get_playing());
+
+        let mut output = [0f32; HOP_S];
+        sampler.do_(input.as_ref(), output.as_mut()).unwrap();
+        assert!(output.iter().all(|s| (s - 0.75).abs() < 1e-3));
+
+        // short last block stops playback
+        let mut output = [0f32; HOP_S];
+        sampler.mix(output.as_mut()).unwrap();
+        assert!(!sampler.get_playing());
+
+        sampler.play().unwrap();
+        sampler.stop().unwrap();
+        assert!(!sampler.get_playing());
+    }
+
+    #[test]
+    fn test_multi() {
+        let path = temp_dir().join("aubio-rs-sampler-stereo.wav");
+        let left = [0.25f32; HOP_S];
+        let right = [-0.25f32; HOP_S];
+
+        let mut sink = Sink::new(&path, 0)
+            .unwrap()
+            .with_samplerate(SAMPLERATE)
+            .unwrap()
+            .with_channels(2)
+            .unwrap();
+        let block: &[&[f32]] = &[&left, &right];
+        sink.do_multi(block, HOP_S).unwrap();
+        sink.close().unwrap();
+
+        let mut sampler = Sampler::new(SAMPLERATE, HOP_S)
+            .unwrap()
+            .with_source(&path)
+            .unwrap();
+        sampler.set_playing(true).unwrap();
+
+        let mut left_out = [0f32; HOP_S];
+        let mut right_out = [0f32; HOP_S];
+        sampler
+            .mix_multi(&mut [left_out.as_mut(), right_out.as_mut()])
+            .unwrap();
+
+        assert!(left_out.iter().all(|s| (s - 0.25).abs() < 1e-3));
+        assert!(right_out.iter().all(|s| (s + 0.25).abs() < 1e-3));
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        let mut sampler = Sampler::new(SAMPLERATE, HOP_S).unwrap();
+
+        let not_loaded = Err(Error::InvalidArg {
+            name: "source",
+            value: "not loaded".into(),
+        });
+        assert_eq!(sampler.play(), not_loaded);
+        assert_eq!(sampler.set_playing(true), not_loaded);
+        assert_eq!(
+            sampler.load(temp_dir().join("aubio-rs-missing.wav")),
+            Err(Error::FailedIo)
+        );
+
+        let mut output = [0f32; HOP_S / 2];
+        assert_eq!(
+            sampler.mix(output.as_mut()),
+            Err(Error::MismatchSize {
+                expected: HOP_S,
+                actual: HOP_S / 2
+            })
+        );
+
+        let path = temp_dir().join("aubio-rs-sampler-reload.wav");
+        let mut sink = Sink::new(&path, SAMPLERATE).unwrap();
+        sink.do_([0.5f32; HOP_S].as_ref(), HOP_S).unwrap();
+        sink.close().unwrap();
+
+        sampler.load(&path).unwrap();
+        assert_eq!(
+            sampler.load(temp_dir().join("aubio-rs-missing.wav")),
+            Err(Error::FailedIo)
+        );
+        assert_eq!(sampler.play(), not_loaded);
+    }
+}
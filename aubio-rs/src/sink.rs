@@ -0,0 +1,252 @@
+use crate::{
+    check_init, ffi, path_to_cstring,
+    vec::{FMat, FMatVecs, FVec},
+    Error, Result, Status,
+};
+
+use std::path::Path;
+
+/**
+ * Media sink object
+ *
+ * Writes audio samples to a file.
+ *
+ * Depending on how _aubio_ was compiled, the following backends are available:
+ *
+ * - libsndfile
+ * - CoreAudio
+ * - built-in WAV writer
+ */
+pub struct Sink {
+    sink: *mut ffi::aubio_sink_t,
+}
+
+impl Drop for Sink {
+    fn drop(&mut self) {
+        unsafe { ffi::del_aubio_sink(self.sink) }
+    }
+}
+
+// Backend handles (file, encoder contexts) are owned by the sink and may be used from any thread
+unsafe impl Send for Sink {}
+
+impl Sink {
+    /**
+     * Create media sink object
+     *
+     * - `path` The file path to write to
+     * - `sample_rate` Sampling rate to write the file at
+     *
+     * If `sample_rate` is 0, the creation of the file will be delayed until
+     * both `preset_samplerate()` and `preset_channels()` have been called.
+     */
+    pub fn new<P: AsRef<Path>>(path: P, sample_rate: u32) -> Result<Self> {
+        let path = path_to_cstring(path.as_ref())?;
+
+        let sink = check_init(|| unsafe {
+            ffi::new_aubio_sink(path.as_ptr(), sample_rate as ffi::uint_t)
+        })?;
+
+        Ok(Self { sink })
+    }
+
+    /**
+     * Preset sink sampling rate
+     */
+    pub fn with_samplerate(mut self, sample_rate: u32) -> Result<Self> {
+        self.preset_samplerate(sample_rate).map(|_| self)
+    }
+
+    /**
+     * Preset sink channels
+     */
+    pub fn with_channels(mut self, channels: usize) -> Result<Self> {
+        self.preset_channels(channels).map(|_| self)
+    }
+
+    /**
+     * Preset sink sampling rate, in Hz
+     *
+     * The sink should have been created using a sampling rate of 0.
+     *
+     * The file will be opened only when both sampling rate and channels have been set.
+     */
+    pub fn preset_samplerate(&mut self, sample_rate: u32) -> Status {
+        if 0 == unsafe { ffi::aubio_sink_preset_samplerate(self.sink, sample_rate as ffi::uint_t) }
+        {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("sample_rate", sample_rate))
+        }
+    }
+
+    /**
+     * Preset sink channels
+     *
+     * The sink should have been created using a sampling rate of 0.
+     *
+     * The file will be opened only when both sampling rate and channels have been set.
+     */
+    pub fn preset_channels(&mut self, channels: usize) -> Status {
+        if 0 == unsafe { ffi::aubio_sink_preset_channels(self.sink, channels as ffi::uint_t) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("channels", channels))
+        }
+    }
+
+    /**
+     * Get sampling rate of sink, in Hz
+     */
+    pub fn get_samplerate(&self) -> u32 {
+        (unsafe { ffi::aubio_sink_get_samplerate(self.sink) }) as u32
+    }
+
+    /**
+     * Get number of channels of sink
+     */
+    pub fn get_channels(&self) -> usize {
+        (unsafe { ffi::aubio_sink_get_channels(self.sink) }) as usize
+    }
+
+    /**
+     * Write monophonic block of samples
+     *
+     * - `input` Samples to write
+     * - `write` Number of frames to write
+     */
+    pub fn do_<'i, I>(&mut self, input: I, write: usize) -> Status
+    where
+        I: Into<FVec<'i>>,
+    {
+        let input = input.into();
+
+        if input.size() < write {
+            return Err(Error::mismatch_size(write, input.size()));
+        }
+
+        // It seems the second arg have missing const qualifier so we need 'as *mut _' here
+        unsafe { ffi::aubio_sink_do(self.sink, input.as_ptr() as *mut _, write as ffi::uint_t) }
+        Ok(())
+    }
+
+    /**
+     * Write polyphonic block of samples
+     *
+     * - `input` Samples to write (`channels` high)
+     * - `write` Number of frames to write
+     */
+    pub fn do_multi<'i, I>(&mut self, input: I, write: usize) -> Status
+    where
+        I: Into<FMat<'i, FMatVecs>>,
+    {
+        let input = input.into();
+
+        if input.length() < write {
+            return Err(Error::mismatch_size(write, input.length()));
+        }
+
+        // It seems the second arg have missing const qualifier so we need 'as *mut _' here
+        unsafe {
+            ffi::aubio_sink_do_multi(self.sink, input.as_ptr() as *mut _, write as ffi::uint_t)
+        }
+        Ok(())
+    }
+
+    /**
+     * Close sink
+     *
+     * The sink is also closed on drop, but any errors are ignored there.
+     */
+    pub fn close(self) -> Status {
+        if 0 == unsafe { ffi::aubio_sink_close(self.sink) } {
+            Ok(())
+        } else {
+            Err(Error::FailedIo)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use std::env::temp_dir;
+
+    const SAMPLERATE: u32 = 44100;
+    const HOP_S: usize = 256;
+
+    #[test]
+    fn test() {
+        let path = temp_dir().join("aubio-rs-sink-mono.wav");
+        let block = [0.5f32; HOP_S];
+
+        let mut sink = Sink::new(&path, SAMPLERATE).unwrap();
+        assert_eq!(sink.get_samplerate(), SAMPLERATE);
+        assert_eq!(sink.get_channels(), 1);
+
+        for _ in 0..4 {
+            sink.do_(block.as_ref(), HOP_S).unwrap();
+        }
+        sink.do_(block.as_ref(), HOP_S / 2).unwrap();
+        sink.close().unwrap();
+
+        let source = Source::new(&path, 0, HOP_S).unwrap();
+        assert_eq!(source.get_samplerate(), SAMPLERATE);
+        assert_eq!(source.get_duration(), HOP_S * 4 + HOP_S / 2);
+    }
+
+    #[test]
+    fn test_multi() {
+        let path = temp_dir().join("aubio-rs-sink-stereo.wav");
+        let left = [0.25f32; HOP_S];
+        let right = [-0.25f32; HOP_S];
+
+        let mut sink = Sink::new(&path, 0)
+            .unwrap()
+            .with_samplerate(SAMPLERATE)
+            .unwrap()
+            .with_channels(2)
+            .unwrap();
+        assert_eq!(sink.get_channels(), 2);
+
+        let block: &[&[f32]] = &[&left, &right];
+        sink.do_multi(block, HOP_S).unwrap();
+        sink.close().unwrap();
+
+        let mut source = Source::new(&path, 0, HOP_S).unwrap();
+        assert_eq!(source.get_channels(), 2);
+
+        let mut left_out = [0f32; HOP_S];
+        let mut right_out = [0f32; HOP_S];
+        let read = source
+            .do_multi(&mut [left_out.as_mut(), right_out.as_mut()])
+            .unwrap();
+        assert_eq!(read, HOP_S);
+        assert!(left_out.iter().all(|s| (s - 0.25).abs() < 1e-3));
+        assert!(right_out.iter().all(|s| (s + 0.25).abs() < 1e-3));
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        let missing = temp_dir().join("aubio-rs-missing").join("out.wav");
+        assert!(Sink::new(missing, SAMPLERATE).is_err());
+
+        let mut sink = Sink::new(temp_dir().join("aubio-rs-sink-short.wav"), SAMPLERATE).unwrap();
+        assert_eq!(
+            sink.do_([0.0f32; 4].as_ref(), 8),
+            Err(Error::MismatchSize {
+                expected: 8,
+                actual: 4
+            })
+        );
+
+        let block: &[&[f32]] = &[&[0.0; 4]];
+        assert_eq!(
+            sink.do_multi(block, 8),
+            Err(Error::MismatchSize {
+                expected: 8,
+                actual: 4
+            })
+        );
+    }
+}
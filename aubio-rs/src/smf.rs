@@ -0,0 +1,621 @@
+use crate::{midi_note, midi_velocity, Error, Note, NoteEvent, Stamped, Status, Tempo};
+
+use std::io::Write;
+
+/**
+ * Default resolution of MIDI file, in ticks per quarter note
+ */
+pub const DEFAULT_PPQ: u16 = 480;
+
+/**
+ * Default tempo of MIDI file, in beats per minute
+ */
+pub const DEFAULT_BPM: f32 = 120.0;
+
+/**
+ * Standard MIDI file format
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MidiFileFormat {
+    /**
+     * Type 0: all tracks are merged into the single one
+     */
+    Single,
+
+    /**
+     * Type 1: the tempo track followed by each of note tracks
+     */
+    #[default]
+    Multi,
+}
+
+/**
+ * Track of notes for MIDI file
+ *
+ * The positions of notes are in samples of input signal.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MidiTrack {
+    channel: u8,
+    // position, note number and velocity (zero for note-off)
+    events: Vec<(usize, u8, u8)>,
+}
+
+impl MidiTrack {
+    /**
+     * Create empty track
+     *
+     * - `channel` MIDI channel of notes (0..16)
+     */
+    pub fn new(channel: u8) -> Self {
+        Self {
+            channel: channel & 0x0f,
+            events: Vec::new(),
+        }
+    }
+
+    /**
+     * Get MIDI channel of notes
+     */
+    pub fn get_channel(&self) -> u8 {
+        self.channel
+    }
+
+    /**
+     * Add complete note
+     */
+    pub fn add_note(&mut self, note: &NoteEvent) {
+        let velocity = midi_velocity(note.velocity);
+
+        self.events.push((note.start, note.midi_note, velocity));
+        self.events.push((note.end, note.midi_note, 0));
+    }
+
+    /**
+     * Add complete notes
+     */
+    pub fn add_notes<'n>(&mut self, notes: impl IntoIterator<Item = &'n NoteEvent>) {
+        for note in notes {
+            self.add_note(note);
+        }
+    }
+
+    /**
+     * Add notes as they are reported by `Notes::do_result`
+     *
+     * The note with zero velocity turns off the note with same pitch.
+     */
+    pub fn add_detected(&mut self, notes: &Stamped<Vec<Note>>) {
+        for note in &notes.value {
+            let velocity = if note.velocity > 0.0 {
+                midi_velocity(note.velocity)
+            } else {
+                0
+            };
+
+            self.events
+                .push((notes.position, midi_note(note.pitch), velocity));
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /**
+     * Get note events as ticks and channel messages
+     *
+     * The note-offs go before note-ons at the same tick.
+     * Each note lasts at least one tick, so its note-off is never written before its note-on.
+     */
+    fn messages(&self, to_ticks: impl Fn(usize) -> u32) -> Vec<(u32, [u8; 3])> {
+        // tick of note-on of currently playing note of each number
+        let mut starts = [None; 128];
+
+        let mut messages = self
+            .events
+            .iter()
+            .map(|&(position, note, velocity)| {
+                let start = &mut starts[(note & 0x7f) as usize];
+                let mut tick = to_ticks(position);
+
+                if velocity > 0 {
+                    *start = Some(tick);
+                } else if let Some(start) = start.take() {
+                    tick = tick.max(start + 1);
+                }
+
+                let status = if velocity > 0 { 0x90 } else { 0x80 };
+                (tick, [status | self.channel, note, velocity])
+            })
+            .collect::<Vec<_>>();
+
+        messages.sort_by_key(|(tick, message)| (*tick, message[2] > 0));
+        messages
+    }
+}
+
+/**
+ * Standard MIDI file writer
+ *
+ * ```
+ * # extern crate aubio_lib;
+ * use aubio_rs::{MidiFile, MidiTrack, NoteEvent};
+ *
+ * let mut track = MidiTrack::new(0);
+ * track.add_note(&NoteEvent {
+ *     midi_note: 69,
+ *     start: 0,
+ *     end: 22050,
+ *     velocity: 100.0,
+ *     pitch_hz: 440.0,
+ * });
+ *
+ * let mut file = MidiFile::new(44100).with_ppq(96);
+ * file.add_track(track);
+ *
+ * let mut data = Vec::new();
+ * file.write(&mut data).unwrap();
+ * ```
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct MidiFile {
+    format: MidiFileFormat,
+    ppq: u16,
+    bpm: f32,
+    sample_rate: u32,
+    tracks: Vec<MidiTrack>,
+}
+
+impl MidiFile {
+    /**
+     * Create MIDI file with default format, resolution and tempo
+     *
+     * - `sample_rate` Sampling rate of the signal which positions of notes are related to
+     */
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            format: MidiFileFormat::default(),
+            ppq: DEFAULT_PPQ,
+            bpm: DEFAULT_BPM,
+            sample_rate,
+            tracks: Vec::new(),
+        }
+    }
+
+    /**
+     * Set file format
+     */
+    pub fn with_format(mut self, format: MidiFileFormat) -> Self {
+        self.set_format(format);
+        self
+    }
+
+    /**
+     * Set resolution, in ticks per quarter note
+     */
+    pub fn with_ppq(mut self, ppq: u16) -> Self {
+        self.set_ppq(ppq);
+        self
+    }
+
+    /**
+     * Set tempo, in beats per minute
+     */
+    pub fn with_bpm(mut self, bpm: f32) -> Self {
+        self.set_bpm(bpm);
+        self
+    }
+
+    /**
+     * Set tempo which has been detected
+     */
+    pub fn with_tempo(mut self, tempo: &Tempo) -> Self {
+        self.set_tempo(tempo);
+        self
+    }
+
+    /**
+     * Get file format
+     */
+    pub fn get_format(&self) -> MidiFileFormat {
+        self.format
+    }
+
+    /**
+     * Set file format
+     */
+    pub fn set_format(&mut self, format: MidiFileFormat) {
+        self.format = format;
+    }
+
+    /**
+     * Get resolution, in ticks per quarter note
+     */
+    pub fn get_ppq(&self) -> u16 {
+        self.ppq
+    }
+
+    /**
+     * Set resolution, in ticks per quarter note
+     *
+     * The resolution should be in range `1..0x8000`, other values are clamped.
+     */
+    pub fn set_ppq(&mut self, ppq: u16) {
+        self.ppq = ppq.clamp(1, 0x7fff);
+    }
+
+    /**
+     * Get tempo, in beats per minute
+     */
+    pub fn get_bpm(&self) -> f32 {
+        self.bpm
+    }
+
+    /**
+     * Set tempo, in beats per minute
+     *
+     * The tempo which is not positive is ignored.
+     */
+    pub fn set_bpm(&mut self, bpm: f32) {
+        if bpm > 0.0 {
+            self.bpm = bpm;
+        }
+    }
+
+    /**
+     * Set tempo which has been detected
+     *
+     * The tempo is kept unchanged when `Tempo::get_bpm` is not available yet.
+     */
+    pub fn set_tempo(&mut self, tempo: &Tempo) {
+        self.set_bpm(tempo.get_bpm());
+    }
+
+    /**
+     * Add track of notes
+     */
+    pub fn add_track(&mut self, track: MidiTrack) {
+        self.tracks.push(track);
+    }
+
+    /**
+     * Get tracks of notes
+     */
+    pub fn tracks(&self) -> &[MidiTrack] {
+        &self.tracks
+    }
+
+    /**
+     * Convert position, in samples, to ticks
+     */
+    pub fn to_ticks(&self, position: usize) -> u32 {
+        let quarters = position as f64 / self.sample_rate.max(1) as f64 * self.bpm as f64 / 60.0;
+
+        (quarters * self.ppq as f64).round() as u32
+    }
+
+    /**
+     * Write MIDI file
+     */
+    pub fn write(&self, mut writer: impl Write) -> Status {
+        writer
+            .write_all(&self.to_bytes())
+            .map_err(|_| Error::FailedIo)
+    }
+
+    /**
+     * Get contents of MIDI file
+     */
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let tracks = self.tracks.iter().filter(|track| !track.is_empty());
+
+        let chunks = match self.format {
+            MidiFileFormat::Single => {
+                let mut messages = tracks
+                    .flat_map(|track| track.messages(|position| self.to_ticks(position)))
+                    .collect::<Vec<_>>();
+
+                messages.sort_by_key(|(tick, message)| (*tick, message[2] > 0));
+
+                vec![self.track_chunk(true, &messages)]
+            }
+            MidiFileFormat::Multi => Some(self.track_chunk(true, &[]))
+                .into_iter()
+                .chain(tracks.map(|track| {
+                    self.track_chunk(false, &track.messages(|position| self.to_ticks(position)))
+                }))
+                .collect(),
+        };
+
+        let format: u16 = match self.format {
+            MidiFileFormat::Single => 0,
+            MidiFileFormat::Multi => 1,
+        };
+
+        let mut data = Vec::new();
+
+        data.extend_from_slice(b"MThd");
+        data.extend_from_slice(&6u32.to_be_bytes());
+        data.extend_from_slice(&format.to_be_bytes());
+        data.extend_from_slice(&(chunks.len() as u16).to_be_bytes());
+        data.extend_from_slice(&self.ppq.to_be_bytes());
+
+        for chunk in chunks {
+            data.extend_from_slice(b"MTrk");
+            data.extend_from_slice(&(chunk.len() as u32).to_be_bytes());
+            data.extend_from_slice(&chunk);
+        }
+
+        data
+    }
+
+    fn track_chunk(&self, with_tempo: bool, messages: &[(u32, [u8; 3])]) -> Vec<u8> {
+        let mut chunk = Vec::new();
+
+        if with_tempo {
+            let tempo = (60_000_000.0 / self.bpm as f64).round() as u32;
+
+            chunk.extend_from_slice(&[0x00, 0xff, 0x51, 0x03]);
+            chunk.extend_from_slice(&tempo.min(0xff_ffff).to_be_bytes()[1..]);
+        }
+
+        let mut last_tick = 0;
+
+        for (tick, message) in messages {
+            write_var_len(&mut chunk, tick - last_tick);
+            chunk.extend_from_slice(message);
+            last_tick = *tick;
+        }
+
+        // end of track
+        chunk.extend_from_slice(&[0x00, 0xff, 0x2f, 0x00]);
+        chunk
+    }
+}
+
+/**
+ * Write variable-length quantity
+ */
+fn write_var_len(data: &mut Vec<u8>, value: u32) {
+    let mut bytes = [0u8; 5];
+    let mut start = bytes.len() - 1;
+    let mut value = value;
+
+    bytes[start] = (value & 0x7f) as u8;
+
+    while value > 0x7f {
+        value >>= 7;
+        start -= 1;
+        bytes[start] = 0x80 | (value & 0x7f) as u8;
+    }
+
+    data.extend_from_slice(&bytes[start..]);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    struct Reader<'a>(&'a [u8]);
+
+    impl<'a> Reader<'a> {
+        fn bytes(&mut self, len: usize) -> &'a [u8] {
+            let (bytes, rest) = self.0.split_at(len);
+            self.0 = rest;
+            bytes
+        }
+
+        fn u8(&mut self) -> u8 {
+            self.bytes(1)[0]
+        }
+
+        fn u16(&mut self) -> u16 {
+            u16::from_be_bytes([self.u8(), self.u8()])
+        }
+
+        fn u32(&mut self) -> u32 {
+            u32::from_be_bytes([self.u8(), self.u8(), self.u8(), self.u8()])
+        }
+
+        fn var_len(&mut self) -> u32 {
+            let mut value = 0;
+            loop {
+                let byte = self.u8();
+                value = (value << 7) | (byte & 0x7f) as u32;
+                if byte & 0x80 == 0 {
+                    return value;
+                }
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Tempo(u32),
+        Message(u32, [u8; 3]),
+    }
+
+    // parse MIDI file into format, resolution and tracks of events with absolute ticks
+    fn parse(data: &[u8]) -> (u16, u16, Vec<Vec<Event>>) {
+        let mut reader = Reader(data);
+
+        assert_eq!(reader.bytes(4), b"MThd");
+        assert_eq!(reader.u32(), 6);
+        let format = reader.u16();
+        let ntracks = reader.u16();
+        let ppq = reader.u16();
+
+        let tracks = (0..ntracks)
+            .map(|_| {
+                assert_eq!(reader.bytes(4), b"MTrk");
+                let len = reader.u32() as usize;
+                let mut track = Reader(reader.bytes(len));
+                let mut events = Vec::new();
+                let mut tick = 0;
+
+                loop {
+                    tick += track.var_len();
+                    match track.u8() {
+                        0xff => {
+                            let kind = track.u8();
+                            let len = track.var_len() as usize;
+                            let data = track.bytes(len);
+                            match kind {
+                                0x2f => break,
+                                0x51 => events.push(Event::Tempo(u32::from_be_bytes([
+                                    0, data[0], data[1], data[2],
+                                ]))),
+                                _ => panic!("unexpected meta event {}", kind),
+                            }
+                        }
+                        status => {
+                            events.push(Event::Message(tick, [status, track.u8(), track.u8()]))
+                        }
+                    }
+                }
+
+                assert!(track.0.is_empty());
+                events
+            })
+            .collect();
+
+        assert!(reader.0.is_empty());
+        (format, ppq, tracks)
+    }
+
+    fn note(midi_note: u8, start: usize, end: usize, velocity: f32) -> NoteEvent {
+        NoteEvent {
+            midi_note,
+            start,
+            end,
+            velocity,
+            pitch_hz: 440.0 * 2f32.powf((midi_note as f32 - 69.0) / 12.0),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        const SR: u32 = 48000;
+
+        let mut melody = MidiTrack::new(0);
+        // a quarter note at 120 bpm lasts 0.5s
+        melody.add_note(&note(60, 0, 24000, 100.4));
+        melody.add_note(&note(64, 24000, 48000, 80.0));
+
+        let mut bass = MidiTrack::new(1);
+        bass.add_detected(&Stamped {
+            index: 0,
+            position: 12000,
+            time: 0.25,
+            value: vec![Note {
+                pitch: 36.2,
+                velocity: 127.0,
+            }],
+        });
+        bass.add_detected(&Stamped {
+            index: 1,
+            position: 36000,
+            time: 0.75,
+            value: vec![Note {
+                pitch: 36.0,
+                velocity: 0.0,
+            }],
+        });
+
+        let mut file = MidiFile::new(SR).with_ppq(96);
+        file.add_track(melody);
+        file.add_track(bass);
+        file.add_track(MidiTrack::new(2));
+
+        let (format, ppq, tracks) = parse(&file.to_bytes());
+
+        assert_eq!(format, 1);
+        assert_eq!(ppq, 96);
+        assert_eq!(
+            tracks,
+            vec![
+                vec![Event::Tempo(500_000)],
+                vec![
+                    Event::Message(0, [0x90, 60, 100]),
+                    Event::Message(96, [0x80, 60, 0]),
+                    Event::Message(96, [0x90, 64, 80]),
+                    Event::Message(192, [0x80, 64, 0]),
+                ],
+                vec![
+                    Event::Message(48, [0x91, 36, 127]),
+                    Event::Message(144, [0x81, 36, 0]),
+                ],
+            ]
+        );
+
+        file.set_format(MidiFileFormat::Single);
+        file.set_bpm(60.0);
+
+        let mut data = Vec::new();
+        file.write(&mut data).unwrap();
+
+        let (format, ppq, tracks) = parse(&data);
+
+        assert_eq!(format, 0);
+        assert_eq!(ppq, 96);
+        assert_eq!(
+            tracks,
+            vec![vec![
+                Event::Tempo(1_000_000),
+                Event::Message(0, [0x90, 60, 100]),
+                Event::Message(24, [0x91, 36, 127]),
+                Event::Message(48, [0x80, 60, 0]),
+                Event::Message(48, [0x90, 64, 80]),
+                Event::Message(72, [0x81, 36, 0]),
+                Event::Message(96, [0x80, 64, 0]),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_short_note() {
+        // a tick lasts 250 samples
+        let mut file = MidiFile::new(48000).with_ppq(96);
+        let mut track = MidiTrack::new(0);
+        track.add_note(&note(60, 1000, 1050, 100.0));
+        track.add_note(&note(64, 1050, 2000, 100.0));
+        file.add_track(track);
+
+        let expected = vec![
+            Event::Message(4, [0x90, 60, 100]),
+            Event::Message(4, [0x90, 64, 100]),
+            Event::Message(5, [0x80, 60, 0]),
+            Event::Message(8, [0x80, 64, 0]),
+        ];
+
+        let (_, _, tracks) = parse(&file.to_bytes());
+        assert_eq!(tracks[1], expected);
+
+        file.set_format(MidiFileFormat::Single);
+
+        let (_, _, tracks) = parse(&file.to_bytes());
+        assert_eq!(tracks[0][1..], expected[..]);
+    }
+
+    #[test]
+    fn test_var_len() {
+        let mut file = MidiFile::new(1000).with_ppq(1000).with_bpm(60.0);
+        let mut track = MidiTrack::new(0);
+        track.add_note(&note(60, 0, 200_000, 64.0));
+        file.add_track(track);
+
+        let (_, _, tracks) = parse(&file.to_bytes());
+
+        assert_eq!(tracks[1][1], Event::Message(200_000, [0x80, 60, 0]));
+    }
+
+    #[test]
+    fn test_tempo() {
+        let tempo = Tempo::new(OnsetMode::SpecFlux, 1024, 512, 44100).unwrap();
+
+        // no beats has been detected yet
+        let file = MidiFile::new(44100).with_bpm(90.0).with_tempo(&tempo);
+        assert_eq!(file.get_bpm(), 90.0);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{
+    check_init, ffi, path_to_cstring,
+    vec::{FMatMut, FMatVecsMut, FVecMut},
+    Error, Result, Status,
+};
+
+use std::path::Path;
+
+/**
+ * Media source object
+ *
+ * Reads audio samples from a file or an URI in blocks of `hop_size` frames.
+ *
+ * Depending on how _aubio_ was compiled, the following backends are available:
+ *
+ * - libav
+ * - libsndfile
+ * - CoreAudio
+ * - built-in WAV reader
+ */
+pub struct Source {
+    source: *mut ffi::aubio_source_t,
+    hop_size: usize,
+}
+
+impl Drop for Source {
+    fn drop(&mut self) {
+        unsafe { ffi::del_aubio_source(self.source) }
+    }
+}
+
+// Backend handles (file, decoder contexts) are owned by the source and may be used from any thread
+unsafe impl Send for Source {}
+
+impl Source {
+    /**
+     * Create media source object
+     *
+     * - `path` The file path or URI to read from
+     * - `sample_rate` Sampling rate to view the file at (use 0 for the rate of the file)
+     * - `hop_size` The size of the blocks to read
+     */
+    pub fn new<P: AsRef<Path>>(path: P, sample_rate: u32, hop_size: usize) -> Result<Self> {
+        let path = path_to_cstring(path.as_ref())?;
+
+        let source = check_init(|| unsafe {
+            ffi::new_aubio_source(
+                path.as_ptr(),
+                sample_rate as ffi::uint_t,
+                hop_size as ffi::uint_t,
+            )
+        })?;
+
+        Ok(Self { source, hop_size })
+    }
+
+    /**
+     * Get hop size
+     */
+    pub fn get_hop(&self) -> usize {
+        self.hop_size
+    }
+
+    /**
+     * Get sampling rate of source, in Hz
+     */
+    pub fn get_samplerate(&self) -> u32 {
+        (unsafe { ffi::aubio_source_get_samplerate(self.source) }) as u32
+    }
+
+    /**
+     * Get number of channels of source
+     */
+    pub fn get_channels(&self) -> usize {
+        (unsafe { ffi::aubio_source_get_channels(self.source) }) as usize
+    }
+
+    /**
+     * Get the duration of source, in frames
+     */
+    pub fn get_duration(&self) -> usize {
+        (unsafe { ffi::aubio_source_get_duration(self.source) }) as usize
+    }
+
+    /**
+     * Read monophonic block of samples
+     *
+     * - `output` Output samples (`hop_size` long)
+     *
+     * When the source has more than one channel, channels are down-mixed.
+     *
+     * Returns the number of frames actually read. It equals `hop_size` if enough frames could be read, less otherwise.
+     */
+    pub fn do_<'o, O>(&mut self, output: O) -> Result<usize>
+    where
+        O: Into<FVecMut<'o>>,
+    {
+        let mut output = output.into();
+
+        output.check_size(self.get_hop())?;
+
+        let mut read = 0;
+        unsafe { ffi::aubio_source_do(self.source, output.as_mut_ptr(), &mut read) }
+        Ok(read as usize)
+    }
+
+    /**
+     * Read polyphonic block of samples
+     *
+     * - `output` Output samples (`channels` high and `hop_size` long)
+     *
+     * Returns the number of frames actually read. It equals `hop_size` if enough frames could be read, less otherwise.
+     */
+    pub fn do_multi<'o, O>(&mut self, output: O) -> Result<usize>
+    where
+        O: Into<FMatMut<'o, FMatVecsMut>>,
+    {
+        let mut output = output.into();
+
+        output.check_size(self.get_hop())?;
+
+        let mut read = 0;
+        unsafe { ffi::aubio_source_do_multi(self.source, output.as_mut_ptr(), &mut read) }
+        Ok(read as usize)
+    }
+
+    /**
+     * Seek source to the given position, in frames
+     */
+    pub fn seek(&mut self, position: usize) -> Status {
+        if 0 == unsafe { ffi::aubio_source_seek(self.source, position as ffi::uint_t) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("position", position))
+        }
+    }
+
+    /**
+     * Close source
+     *
+     * The source is also closed on drop, but any errors are ignored there.
+     */
+    pub fn close(self) -> Status {
+        if 0 == unsafe { ffi::aubio_source_close(self.source) } {
+            Ok(())
+        } else {
+            Err(Error::FailedIo)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use hound::{SampleFormat, WavSpec, WavWriter};
+    use std::{env::temp_dir, path::PathBuf};
+
+    const SAMPLERATE: u32 = 44100;
+    const HOP_S: usize = 256;
+
+    fn write_wav(name: &str, channels: u16, frames: usize) -> PathBuf {
+        let path = temp_dir().join(name);
+        let spec = WavSpec {
+            channels,
+            sample_rate: SAMPLERATE,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        let mut writer = WavWriter::create(&path, spec).unwrap();
+        for i in 0..frames {
+            for _ in 0..channels {
+                writer.write_sample((i % 128) as i16 * 64).unwrap();
+            }
+        }
+        writer.finalize().unwrap();
+        path
+    }
+
+    #[test]
+    fn test() {
+        const FRAMES: usize = HOP_S * 4 + HOP_S / 2;
+
+        let path = write_wav("aubio-rs-source-mono.wav", 1, FRAMES);
+        let mut source = Source::new(&path, 0, HOP_S).unwrap();
+
+        assert_eq!(source.get_hop(), HOP_S);
+        assert_eq!(source.get_samplerate(), SAMPLERATE);
+        assert_eq!(source.get_channels(), 1);
+        assert_eq!(source.get_duration(), FRAMES);
+
+        let mut block = [0f32; HOP_S];
+        let mut total = 0;
+        loop {
+            let read = source.do_(block.as_mut()).unwrap();
+            total += read;
+            if read < HOP_S {
+                break;
+            }
+        }
+        assert_eq!(total, FRAMES);
+
+        source.seek(0).unwrap();
+        assert_eq!(source.do_(block.as_mut()).unwrap(), HOP_S);
+
+        source.close().unwrap();
+    }
+
+    #[test]
+    fn test_multi() {
+        let path = write_wav("aubio-rs-source-stereo.wav", 2, HOP_S * 2);
+        let mut source = Source::new(&path, 0, HOP_S).unwrap();
+
+        assert_eq!(source.get_channels(), 2);
+
+        let mut left = [0f32; HOP_S];
+        let mut right = [0f32; HOP_S];
+        let read = source
+            .do_multi(&mut [left.as_mut(), right.as_mut()])
+            .unwrap();
+
+        assert_eq!(read, HOP_S);
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        match Source::new(temp_dir().join("aubio-rs-missing.wav"), 0, HOP_S).err() {
+            Some(Error::FailedInit {
+                message: Some(message),
+            }) => assert!(message.contains("aubio-rs-missing.wav")),
+            error => panic!("unexpected error: {:?}", error),
+        }
+        assert!(Source::new("nul\0byte.wav", 0, HOP_S).is_err());
+    }
+}
@@ -125,16 +125,20 @@ impl FromStr for SpecShape {
             "slope" => Slope,
             "decrease" => Decrease,
             "rolloff" => Rolloff,
-            _ => return Err(Error::InvalidArg),
+            _ => return Err(Error::invalid_arg("SpecShape", src)),
         })
     }
 }
 
+impl_serde_str!(SpecShape);
+
 /**
  * Spectral description object
  */
 pub struct SpecDesc {
     specdesc: *mut ffi::aubio_specdesc_t,
+    method: &'static str,
+    buf_size: usize,
 }
 
 impl Drop for SpecDesc {
@@ -143,6 +147,8 @@ impl Drop for SpecDesc {
     }
 }
 
+unsafe impl Send for SpecDesc {}
+
 impl SpecDesc {
     /**
      * Creation of a spectral description object
@@ -151,12 +157,19 @@ impl SpecDesc {
      * - `buf_size` Length of the input spectrum frame
      */
     pub fn new(method: impl SpecMethod, buf_size: usize) -> Result<Self> {
-        let specdesc =
-            unsafe { ffi::new_aubio_specdesc(method.as_native_cstr(), buf_size as ffi::uint_t) };
+        Self::new_native(method.as_native_str(), buf_size)
+    }
 
-        check_init(specdesc)?;
+    fn new_native(method: &'static str, buf_size: usize) -> Result<Self> {
+        let specdesc = check_init(|| unsafe {
+            ffi::new_aubio_specdesc(method.as_ptr() as *const _, buf_size as ffi::uint_t)
+        })?;
 
-        Ok(Self { specdesc })
+        Ok(Self {
+            specdesc,
+            method,
+            buf_size,
+        })
     }
 
     /**
@@ -193,6 +206,17 @@ impl SpecDesc {
         self.do_(fftgrain, &mut desc)?;
         Ok(desc[0])
     }
+
+    /**
+     * Reset spectral description
+     *
+     * Methods which compare consecutive frames (like `Phase`, `Kl` or `SpecFlux`)
+     * forget the previous frame, by recreating the object with the same method and size.
+     */
+    pub fn reset(&mut self) -> Status {
+        *self = Self::new_native(self.method, self.buf_size)?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -245,4 +269,32 @@ mod test {
         let mut o = SpecDesc::new(SpecShape::Rolloff, WIN).unwrap();
         o.do_(in_.as_ref(), out.as_mut()).unwrap();
     }
+
+    #[test]
+    fn test_reset() {
+        const WIN: usize = 64;
+
+        let frame = |hop: usize| {
+            let norm = (0..WIN / 2 + 1)
+                .map(|i| ((i + hop) % 5) as f32)
+                .collect::<Vec<_>>();
+            vec::CVecBuf::from_parts(norm, vec![0.0; WIN / 2 + 1]).unwrap()
+        };
+
+        let mut o = SpecDesc::new(OnsetMode::SpecFlux, WIN).unwrap();
+
+        for hop in 0..4 {
+            o.do_result(frame(hop).as_cvec()).unwrap();
+        }
+
+        o.reset().unwrap();
+
+        // a fresh descriptor has no previous frame to compare with
+        let mut fresh = SpecDesc::new(OnsetMode::SpecFlux, WIN).unwrap();
+
+        assert_eq!(
+            o.do_result(frame(0).as_cvec()).unwrap(),
+            fresh.do_result(frame(0).as_cvec()).unwrap()
+        );
+    }
 }
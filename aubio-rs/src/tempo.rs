@@ -1,15 +1,109 @@
 use crate::{
-    check_init, ffi,
+    check_buf_hop, check_init, check_sample_rate, ffi,
     vec::{FVec, FVecMut},
     AsNativeStr, OnsetMode, Result, Status,
 };
 
+/**
+ * Tempo detection configuration
+ *
+ * The optional parameters which are `None` keep the defaults
+ * which _aubio_ selects for the beat tracking method.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TempoConfig {
+    /**
+     * Beat tracking method
+     */
+    pub method: OnsetMode,
+
+    /**
+     * Length of FFT
+     */
+    pub buf_size: usize,
+
+    /**
+     * Number of frames between two consecutive runs
+     */
+    pub hop_size: usize,
+
+    /**
+     * Sampling rate of the signal to analyze
+     */
+    pub sample_rate: u32,
+
+    /**
+     * Silence threshold, in dB
+     */
+    pub silence: Option<f32>,
+
+    /**
+     * Peak picking threshold
+     */
+    pub threshold: Option<f32>,
+
+    /**
+     * Delay, in samples
+     */
+    pub delay: Option<isize>,
+
+    /**
+     * Number of tatum per beat
+     */
+    pub tatum_signature: Option<u32>,
+}
+
+impl TempoConfig {
+    /**
+     * Create tempo detection configuration with default parameters
+     */
+    pub fn new(method: OnsetMode, buf_size: usize, hop_size: usize, sample_rate: u32) -> Self {
+        Self {
+            method,
+            buf_size,
+            hop_size,
+            sample_rate,
+            silence: None,
+            threshold: None,
+            delay: None,
+            tatum_signature: None,
+        }
+    }
+
+    /**
+     * Create tempo detection object using configuration
+     */
+    pub fn build(&self) -> Result<Tempo> {
+        let mut tempo = Tempo::new(self.method, self.buf_size, self.hop_size, self.sample_rate)?;
+
+        if let Some(silence) = self.silence {
+            tempo.set_silence(silence);
+        }
+        if let Some(threshold) = self.threshold {
+            tempo.set_threshold(threshold);
+        }
+        if let Some(delay) = self.delay {
+            tempo.set_delay(delay);
+        }
+        if let Some(signature) = self.tatum_signature {
+            tempo.set_tatum_signature(signature);
+        }
+
+        Ok(tempo)
+    }
+}
+
 /**
  * Tempo detection object
  */
 pub struct Tempo {
     tempo: *mut ffi::aubio_tempo_t,
+    method: OnsetMode,
+    buf_size: usize,
     hop_size: usize,
+    sample_rate: u32,
+    tatum_signature: Option<u32>,
 }
 
 impl Drop for Tempo {
@@ -18,6 +112,8 @@ impl Drop for Tempo {
     }
 }
 
+unsafe impl Send for Tempo {}
+
 impl Tempo {
     /**
      * Create tempo detection object
@@ -33,19 +129,27 @@ impl Tempo {
         hop_size: usize,
         sample_rate: u32,
     ) -> Result<Self> {
+        check_buf_hop(buf_size, hop_size)?;
+        check_sample_rate(sample_rate)?;
+
         // TODO: Use `SpecFlux` as default method for Tempo
-        let tempo = unsafe {
+        let tempo = check_init(|| unsafe {
             ffi::new_aubio_tempo(
                 method.as_native_cstr(),
                 buf_size as ffi::uint_t,
                 hop_size as ffi::uint_t,
                 sample_rate as ffi::uint_t,
             )
-        };
-
-        check_init(tempo)?;
-
-        Ok(Self { tempo, hop_size })
+        })?;
+
+        Ok(Self {
+            tempo,
+            method,
+            buf_size,
+            hop_size,
+            sample_rate,
+            tatum_signature: None,
+        })
     }
 
     /**
@@ -95,6 +199,31 @@ impl Tempo {
         self.hop_size
     }
 
+    /**
+     * Get beat tracking method
+     */
+    pub fn get_mode(&self) -> OnsetMode {
+        self.method
+    }
+
+    /**
+     * Get current configuration
+     */
+    pub fn config(&self) -> TempoConfig {
+        TempoConfig {
+            method: self.method,
+            buf_size: self.buf_size,
+            hop_size: self.hop_size,
+            sample_rate: self.sample_rate,
+            silence: Some(self.get_silence()),
+            threshold: Some(self.get_threshold()),
+            delay: Some(
+                (unsafe { ffi::aubio_tempo_get_delay(self.tempo) }) as ffi::sint_t as isize,
+            ),
+            tatum_signature: self.tatum_signature,
+        }
+    }
+
     /**
      * Execute tempo detection
      */
@@ -215,6 +344,7 @@ impl Tempo {
         unsafe {
             ffi::aubio_tempo_set_tatum_signature(self.tempo, signature);
         }
+        self.tatum_signature = Some(signature);
     }
 
     /**
@@ -278,4 +408,110 @@ impl Tempo {
     pub fn get_delay_ms(&self) -> f32 {
         unsafe { ffi::aubio_tempo_get_delay_ms(self.tempo) }
     }
+
+    /**
+     * Reset tempo detection
+     *
+     * The beat tracking history is cleared by recreating the detection object
+     * with the same method, sizes, thresholds, delay and tatum signature.
+     */
+    pub fn reset(&mut self) -> Status {
+        *self = self.config().build()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test_config() {
+        let mut config = TempoConfig::new(OnsetMode::SpecDiff, 1024, 512, 44100);
+        config.threshold = Some(0.4);
+        config.delay = Some(-256);
+        config.tatum_signature = Some(3);
+
+        let tempo = config.build().unwrap();
+
+        assert_eq!(tempo.get_mode(), OnsetMode::SpecDiff);
+        assert_eq!(tempo.get_threshold(), 0.4);
+
+        let config = tempo.config();
+        assert_eq!(config.delay, Some(-256));
+        assert_eq!(config.build().unwrap().config(), config);
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        let invalid = |name, value: &str| {
+            Some(Error::InvalidArg {
+                name,
+                value: value.into(),
+            })
+        };
+
+        assert_eq!(
+            Tempo::new(OnsetMode::SpecFlux, 1024, 0, 44100).err(),
+            invalid("hop_size", "0")
+        );
+        assert_eq!(
+            Tempo::new(OnsetMode::SpecFlux, 0, 256, 44100).err(),
+            invalid("buf_size", "0")
+        );
+        assert_eq!(
+            Tempo::new(OnsetMode::SpecFlux, 512, 1024, 44100).err(),
+            invalid("hop_size", "1024")
+        );
+        assert_eq!(
+            Tempo::new(OnsetMode::SpecFlux, 1024, 256, 0).err(),
+            invalid("sample_rate", "0")
+        );
+        if !cfg!(feature = "with-fftw3") {
+            assert_eq!(
+                Tempo::new(OnsetMode::SpecFlux, 1000, 250, 44100).err(),
+                invalid("buf_size", "1000")
+            );
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        const BUF_S: usize = 1024;
+        const HOP_S: usize = 256;
+
+        let hop = |click: bool| {
+            let mut input = [0f32; HOP_S];
+            input[0] = if click { 1.0 } else { 0.0 };
+            input
+        };
+
+        let mut tempo = Tempo::new(OnsetMode::SpecFlux, BUF_S, HOP_S, 44100).unwrap();
+        tempo.set_tatum_signature(3);
+        tempo.set_threshold(0.4);
+
+        // a click every 8 hops
+        for _beat in 0..8 {
+            tempo.do_result(hop(true).as_ref()).unwrap();
+            for _i in 1..8 {
+                tempo.do_result(hop(false).as_ref()).unwrap();
+            }
+        }
+
+        tempo.reset().unwrap();
+        assert_eq!(tempo.config().tatum_signature, Some(3));
+        assert_eq!(tempo.get_threshold(), 0.4);
+
+        // a fresh detector only sees the first hop
+        let mut fresh = Tempo::new(OnsetMode::SpecFlux, BUF_S, HOP_S, 44100).unwrap();
+        fresh.set_tatum_signature(3);
+        fresh.set_threshold(0.4);
+
+        assert_eq!(
+            tempo.do_result(hop(true).as_ref()).unwrap(),
+            fresh.do_result(hop(true).as_ref()).unwrap()
+        );
+        assert_eq!(tempo.get_last(), fresh.get_last());
+        assert_eq!(tempo.get_bpm(), fresh.get_bpm());
+    }
 }
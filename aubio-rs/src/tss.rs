@@ -0,0 +1,284 @@
+use crate::{
+    check_init, ffi,
+    vec::{CVec, CVecMut},
+    Result, Status,
+};
+
+/**
+ * Transient / Steady-state Separation object
+ *
+ * This object splits a spectral frame (as computed by `PVoc::do_`)
+ * into its transient and steady-state components.
+ *
+ * The phase of the input frame is kept in both outputs,
+ * so that each of them can be resynthesised using `PVoc::rdo`.
+ *
+ * See article: Transient/steady-state separation with phase vocoder,
+ * C. Duxbury, M. Davies, and M. Sandler, DAFx 2002.
+ */
+pub struct Tss {
+    tss: *mut ffi::aubio_tss_t,
+    buf_size: usize,
+    hop_size: usize,
+    threshold: Option<f32>,
+    alpha: Option<f32>,
+    beta: Option<f32>,
+}
+
+impl Drop for Tss {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::del_aubio_tss(self.tss);
+        }
+    }
+}
+
+unsafe impl Send for Tss {}
+
+impl Tss {
+    /**
+     * Create transient / steady-state separation object
+     *
+     * - `buf_size` Buffer size of the phase vocoder
+     * - `hop_size` Hop size of the phase vocoder
+     */
+    pub fn new(buf_size: usize, hop_size: usize) -> Result<Self> {
+        let tss = check_init(|| unsafe {
+            ffi::new_aubio_tss(buf_size as ffi::uint_t, hop_size as ffi::uint_t)
+        })?;
+
+        Ok(Self {
+            tss,
+            buf_size,
+            hop_size,
+            threshold: None,
+            alpha: None,
+            beta: None,
+        })
+    }
+
+    /**
+     * Set transient / steady-state separation threshold
+     */
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.set_threshold(threshold);
+        self
+    }
+
+    /**
+     * Set parameter alpha
+     */
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.set_alpha(alpha);
+        self
+    }
+
+    /**
+     * Set parameter beta
+     */
+    pub fn with_beta(mut self, beta: f32) -> Self {
+        self.set_beta(beta);
+        self
+    }
+
+    /**
+     * Get buffer size
+     */
+    pub fn get_buf(&self) -> usize {
+        self.buf_size
+    }
+
+    /**
+     * Get hop size
+     */
+    pub fn get_hop(&self) -> usize {
+        self.hop_size
+    }
+
+    /**
+     * Split input into transient and steady-state components
+     *
+     * - `input` Input spectral frame (`buf_size` long)
+     * - `trans` Output transient components (`buf_size` long)
+     * - `stead` Output steady-state components (`buf_size` long)
+     */
+    pub fn do_<'i, 't, 's, I, T, S>(&mut self, input: I, trans: T, stead: S) -> Status
+    where
+        I: Into<CVec<'i>>,
+        T: Into<CVecMut<'t>>,
+        S: Into<CVecMut<'s>>,
+    {
+        let input = input.into();
+        let mut trans = trans.into();
+        let mut stead = stead.into();
+
+        input.check_size(self.get_buf())?;
+        trans.check_size(self.get_buf())?;
+        stead.check_size(self.get_buf())?;
+
+        unsafe {
+            ffi::aubio_tss_do(
+                self.tss,
+                input.as_ptr(),
+                trans.as_mut_ptr(),
+                stead.as_mut_ptr(),
+            );
+        }
+        Ok(())
+    }
+
+    /**
+     * Set transient / steady-state separation threshold
+     */
+    pub fn set_threshold(&mut self, threshold: f32) {
+        unsafe {
+            ffi::aubio_tss_set_threshold(self.tss, threshold);
+        }
+        self.threshold = Some(threshold);
+    }
+
+    /**
+     * Set parameter alpha
+     */
+    pub fn set_alpha(&mut self, alpha: f32) {
+        unsafe {
+            ffi::aubio_tss_set_alpha(self.tss, alpha);
+        }
+        self.alpha = Some(alpha);
+    }
+
+    /**
+     * Set parameter beta
+     */
+    pub fn set_beta(&mut self, beta: f32) {
+        unsafe {
+            ffi::aubio_tss_set_beta(self.tss, beta);
+        }
+        self.beta = Some(beta);
+    }
+
+    /**
+     * Reset transient / steady-state separation
+     *
+     * The previous spectral frames are cleared by recreating the object
+     * with the same sizes, threshold, alpha and beta.
+     */
+    pub fn reset(&mut self) -> Status {
+        let mut tss = Self::new(self.buf_size, self.hop_size)?;
+
+        if let Some(threshold) = self.threshold {
+            tss.set_threshold(threshold);
+        }
+        if let Some(alpha) = self.alpha {
+            tss.set_alpha(alpha);
+        }
+        if let Some(beta) = self.beta {
+            tss.set_beta(beta);
+        }
+
+        *self = tss;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test() {
+        const WIN_S: usize = 512; // window size
+        const HOP_S: usize = WIN_S / 4; // hop size
+
+        let mut in_ = [0f32; HOP_S]; // input buffer
+        let mut fftgrain = carr!(WIN_S); // fft norm and phase
+        let mut ctrans = carr!(WIN_S); // transient components
+        let mut cstead = carr!(WIN_S); // steady-state components
+        let mut trans = [0f32; HOP_S]; // transient output
+        let mut stead = [0f32; HOP_S]; // steady-state output
+
+        let mut pv = PVoc::new(WIN_S, HOP_S).unwrap();
+        let mut pvt = PVoc::new(WIN_S, HOP_S).unwrap();
+        let mut pvs = PVoc::new(WIN_S, HOP_S).unwrap();
+
+        let mut tss = Tss::new(WIN_S, HOP_S)
+            .unwrap()
+            .with_threshold(0.25)
+            .with_alpha(3.0)
+            .with_beta(4.0);
+
+        assert_eq!(tss.get_buf(), WIN_S);
+        assert_eq!(tss.get_hop(), HOP_S);
+
+        for i in 0..10 {
+            in_[0] = if i % 4 == 0 { 1.0 } else { 0.0 };
+
+            pv.do_(in_.as_ref(), fftgrain.as_mut()).unwrap();
+            tss.do_(fftgrain.as_ref(), ctrans.as_mut(), cstead.as_mut())
+                .unwrap();
+            pvt.rdo(ctrans.as_ref(), trans.as_mut()).unwrap();
+            pvs.rdo(cstead.as_ref(), stead.as_mut()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_bufs() {
+        const WIN_S: usize = 64;
+
+        let fftgrain = vec::CVecBuf::new(WIN_S);
+        let mut trans = vec::CVecBuf::new(WIN_S);
+        let mut stead = vec::CVecBuf::new(WIN_S);
+
+        let mut tss = Tss::new(WIN_S, WIN_S / 2).unwrap();
+
+        tss.do_(&fftgrain, &mut trans, &mut stead).unwrap();
+
+        assert!(trans.norm().iter().all(|v| *v == 0.0));
+        assert!(stead.norm().iter().all(|v| *v == 0.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        const WIN_S: usize = 64;
+        const HOP_S: usize = WIN_S / 4;
+
+        let frame = |hop: usize| {
+            let norm = (0..WIN_S / 2 + 1)
+                .map(|i| ((i + hop) % 7) as f32)
+                .collect::<Vec<_>>();
+            let phas = (0..WIN_S / 2 + 1)
+                .map(|i| (i * hop) as f32 * 0.1)
+                .collect::<Vec<_>>();
+            vec::CVecBuf::from_parts(norm, phas).unwrap()
+        };
+        let mut trans = vec::CVecBuf::new(WIN_S);
+        let mut stead = vec::CVecBuf::new(WIN_S);
+
+        let mut tss = Tss::new(WIN_S, HOP_S).unwrap();
+        tss.set_alpha(3.0);
+
+        for hop in 0..4 {
+            tss.do_(frame(hop).as_cvec(), &mut trans, &mut stead)
+                .unwrap();
+        }
+
+        tss.reset().unwrap();
+        assert_eq!(tss.get_buf(), WIN_S);
+        assert_eq!(tss.get_hop(), HOP_S);
+        assert_eq!(tss.alpha, Some(3.0));
+
+        // a fresh object only sees the first frame
+        let mut fresh = Tss::new(WIN_S, HOP_S).unwrap();
+        fresh.set_alpha(3.0);
+        let mut expected_trans = vec::CVecBuf::new(WIN_S);
+        let mut expected_stead = vec::CVecBuf::new(WIN_S);
+
+        tss.do_(frame(0).as_cvec(), &mut trans, &mut stead).unwrap();
+        fresh
+            .do_(frame(0).as_cvec(), &mut expected_trans, &mut expected_stead)
+            .unwrap();
+
+        assert_eq!(trans, expected_trans);
+        assert_eq!(stead, expected_stead);
+    }
+}
@@ -1,29 +1,77 @@
+use crate::log;
+
 use std::{
     error::Error as StdError,
+    ffi::CString,
     fmt::{Display, Formatter, Result as FmtResult},
     os::raw::c_char,
+    path::Path,
     result::Result as StdResult,
 };
 
 /**
  * The error type
  */
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Error {
     /**
      * Failed to initialize object
      */
-    FailedInit,
+    FailedInit {
+        /**
+         * Error message which _aubio_ reported while creating the object
+         */
+        message: Option<String>,
+    },
 
     /**
      * Data size mismatched
      */
-    MismatchSize,
+    MismatchSize {
+        /**
+         * Expected size (or minimum size) of data
+         */
+        expected: usize,
+
+        /**
+         * Actual size of data
+         */
+        actual: usize,
+    },
 
     /**
      * Invalid argument
      */
-    InvalidArg,
+    InvalidArg {
+        /**
+         * Name of argument
+         */
+        name: &'static str,
+
+        /**
+         * Value of argument
+         */
+        value: String,
+    },
+
+    /**
+     * Failed to read, write or close media
+     */
+    FailedIo,
+}
+
+impl Error {
+    pub(crate) fn mismatch_size(expected: usize, actual: usize) -> Self {
+        Error::MismatchSize { expected, actual }
+    }
+
+    pub(crate) fn invalid_arg(name: &'static str, value: impl ToString) -> Self {
+        Error::InvalidArg {
+            name,
+            value: value.to_string(),
+        }
+    }
 }
 
 impl StdError for Error {}
@@ -31,9 +79,17 @@ impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         use self::Error::*;
         match self {
-            FailedInit => "creation error".fmt(f),
-            MismatchSize => "data size mismatch".fmt(f),
-            InvalidArg => "invalid argument".fmt(f),
+            FailedInit { message: None } => "creation error".fmt(f),
+            FailedInit {
+                message: Some(message),
+            } => write!(f, "creation error: {}", message),
+            MismatchSize { expected, actual } => write!(
+                f,
+                "data size mismatch: expected {}, got {}",
+                expected, actual
+            ),
+            InvalidArg { name, value } => write!(f, "invalid argument `{}`: {}", name, value),
+            FailedIo => "i/o error".fmt(f),
         }
     }
 }
@@ -74,10 +130,63 @@ pub trait AsNativeStr {
     }
 }
 
-pub(crate) fn check_init<T>(ptr: *mut T) -> Status {
+/**
+ * Create _aubio_ object, capturing the error messages it reports on failure
+ */
+pub(crate) fn check_init<T>(new: impl FnOnce() -> *mut T) -> Result<*mut T> {
+    let (ptr, message) = log::capture_errors(new);
+
     if ptr.is_null() {
-        Err(Error::FailedInit)
+        Err(Error::FailedInit { message })
     } else {
+        Ok(ptr)
+    }
+}
+
+/**
+ * Check argument, returning `Error::InvalidArg` with its value when it is not valid
+ */
+pub(crate) fn check_arg<T: ToString>(name: &'static str, value: T, valid: bool) -> Status {
+    if valid {
         Ok(())
+    } else {
+        Err(Error::invalid_arg(name, value))
     }
 }
+
+/**
+ * Check size of FFT
+ *
+ * Size should be at least 2. Unless _aubio_ uses __FFTW3__ (see _with-fftw3_ feature),
+ * it also should be a power of two.
+ */
+pub(crate) fn check_fft_size(name: &'static str, size: usize) -> Status {
+    check_arg(name, size, size >= 2)?;
+    check_arg(
+        name,
+        size,
+        cfg!(feature = "with-fftw3") || size.is_power_of_two(),
+    )
+}
+
+/**
+ * Check buffer and hop sizes of phase vocoder based objects
+ */
+pub(crate) fn check_buf_hop(buf_size: usize, hop_size: usize) -> Status {
+    check_arg("hop_size", hop_size, hop_size >= 1)?;
+    check_fft_size("buf_size", buf_size)?;
+    check_arg("hop_size", hop_size, hop_size <= buf_size)
+}
+
+/**
+ * Check sample rate
+ */
+pub(crate) fn check_sample_rate(sample_rate: u32) -> Status {
+    check_arg("sample_rate", sample_rate, sample_rate >= 1)
+}
+
+pub(crate) fn path_to_cstring(path: &Path) -> Result<CString> {
+    path.to_str()
+        .and_then(|path| CString::new(path).ok())
+        .ok_or_else(|| Error::invalid_arg("path", path.display()))
+}
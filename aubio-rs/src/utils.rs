@@ -1,6 +1,7 @@
 use crate::{
     ffi,
-    vec::{FVec, FVecMut},
+    vec::{CVec, CVecMut, FMat, FMatMut, FVec, FVecMut, LVecMut},
+    Error, Status,
 };
 
 /**
@@ -242,7 +243,468 @@ impl<'a> FVec<'a> {
     where
         I: Into<FVecMut<'i>>,
     {
-        let mut input = input.into();
-        unsafe { ffi::fvec_clamp(input.as_mut_ptr(), absmax) };
+        input.into().clamp(absmax);
+    }
+}
+
+impl<'a> FVecMut<'a> {
+    /**
+     * Set all elements to a given value
+     */
+    pub fn set_all(&mut self, value: f32) {
+        unsafe { ffi::fvec_set_all(self.as_mut_ptr(), value) };
+    }
+
+    /**
+     * Set all elements to zero
+     */
+    pub fn zeros(&mut self) {
+        unsafe { ffi::fvec_zeros(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Set all elements to one
+     */
+    pub fn ones(&mut self) {
+        unsafe { ffi::fvec_ones(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Revert order of elements
+     */
+    pub fn rev(&mut self) {
+        unsafe { ffi::fvec_rev(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Apply weight to vector
+     *
+     * If the weight vector is longer than this vector, only the first elements are used.
+     * If the weight vector is shorter, the last elements of this vector are not weighted.
+     */
+    pub fn weight<'w, W>(&mut self, weight: W)
+    where
+        W: Into<FVec<'w>>,
+    {
+        let weight = weight.into();
+        unsafe { ffi::fvec_weight(self.as_mut_ptr(), weight.as_ptr()) };
+    }
+
+    /**
+     * Copy elements from another vector of the same size
+     */
+    pub fn copy_from<'i, I>(&mut self, input: I) -> Status
+    where
+        I: Into<FVec<'i>>,
+    {
+        let input = input.into();
+
+        if input.size() != self.size() {
+            return Err(Error::mismatch_size(self.size(), input.size()));
+        }
+
+        unsafe { ffi::fvec_copy(input.as_ptr(), self.as_mut_ptr()) };
+        Ok(())
+    }
+
+    /**
+     * Compute weighted copy of another vector
+     *
+     * - `input` Input vector
+     * - `weight` Weights vector
+     *
+     * Only the first `min(input, weight, self)` elements are computed.
+     */
+    pub fn weighted_copy<'i, 'w, I, W>(&mut self, input: I, weight: W)
+    where
+        I: Into<FVec<'i>>,
+        W: Into<FVec<'w>>,
+    {
+        let input = input.into();
+        let weight = weight.into();
+        unsafe { ffi::fvec_weighted_copy(input.as_ptr(), weight.as_ptr(), self.as_mut_ptr()) };
+    }
+
+    /**
+     * Clamp the values of a vector within the range -abs(max) ..= abs(max)
+     *
+     * - `absmax` Maximum value over which elements should be clamped
+     */
+    pub fn clamp(&mut self, absmax: f32) {
+        unsafe { ffi::fvec_clamp(self.as_mut_ptr(), absmax) };
+    }
+
+    /**
+     * Compute e ^ x of each element
+     */
+    pub fn exp(&mut self) {
+        unsafe { ffi::fvec_exp(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Compute cos(x) of each element
+     */
+    pub fn cos(&mut self) {
+        unsafe { ffi::fvec_cos(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Compute sin(x) of each element
+     */
+    pub fn sin(&mut self) {
+        unsafe { ffi::fvec_sin(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Compute abs(x) of each element
+     */
+    pub fn abs(&mut self) {
+        unsafe { ffi::fvec_abs(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Compute sqrt(x) of each element
+     */
+    pub fn sqrt(&mut self) {
+        unsafe { ffi::fvec_sqrt(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Compute log10(x) of each element
+     */
+    pub fn log10(&mut self) {
+        unsafe { ffi::fvec_log10(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Compute log(x) of each element
+     */
+    pub fn log(&mut self) {
+        unsafe { ffi::fvec_log(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Compute floor(x) of each element
+     */
+    pub fn floor(&mut self) {
+        unsafe { ffi::fvec_floor(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Compute ceil(x) of each element
+     */
+    pub fn ceil(&mut self) {
+        unsafe { ffi::fvec_ceil(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Compute round(x) of each element
+     */
+    pub fn round(&mut self) {
+        unsafe { ffi::fvec_round(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Raise each element to the given power
+     */
+    pub fn pow(&mut self, power: f32) {
+        unsafe { ffi::fvec_pow(self.as_mut_ptr(), power) };
+    }
+}
+
+/**
+ * The norm or phas only operations does nothing when the corresponding part is missing
+ * (see `CVecNormMut` and `CVecPhasMut`).
+ */
+impl<'a> CVecMut<'a> {
+    /**
+     * Set all norm elements to a given value
+     */
+    pub fn norm_set_all(&mut self, value: f32) {
+        if self.has_norm() {
+            unsafe { ffi::cvec_norm_set_all(self.as_mut_ptr(), value) };
+        }
+    }
+
+    /**
+     * Set all norm elements to zero
+     */
+    pub fn norm_zeros(&mut self) {
+        if self.has_norm() {
+            unsafe { ffi::cvec_norm_zeros(self.as_mut_ptr()) };
+        }
+    }
+
+    /**
+     * Set all norm elements to one
+     */
+    pub fn norm_ones(&mut self) {
+        if self.has_norm() {
+            unsafe { ffi::cvec_norm_ones(self.as_mut_ptr()) };
+        }
+    }
+
+    /**
+     * Set all phas elements to a given value
+     */
+    pub fn phas_set_all(&mut self, value: f32) {
+        if self.has_phas() {
+            unsafe { ffi::cvec_phas_set_all(self.as_mut_ptr(), value) };
+        }
+    }
+
+    /**
+     * Set all phas elements to zero
+     */
+    pub fn phas_zeros(&mut self) {
+        if self.has_phas() {
+            unsafe { ffi::cvec_phas_zeros(self.as_mut_ptr()) };
+        }
+    }
+
+    /**
+     * Set all phas elements to one
+     */
+    pub fn phas_ones(&mut self) {
+        if self.has_phas() {
+            unsafe { ffi::cvec_phas_ones(self.as_mut_ptr()) };
+        }
+    }
+
+    /**
+     * Set all norm and phas elements to zero
+     */
+    pub fn zeros(&mut self) {
+        self.norm_zeros();
+        self.phas_zeros();
+    }
+
+    /**
+     * Take logarithmic magnitude of norm elements
+     *
+     * - `lambda` Value to use for normalisation
+     *
+     * S_k = log(λ * S_k + 1)
+     */
+    pub fn logmag(&mut self, lambda: f32) {
+        if self.has_norm() {
+            unsafe { ffi::cvec_logmag(self.as_mut_ptr(), lambda) };
+        }
+    }
+
+    /**
+     * Copy elements from another complex vector of the same size
+     */
+    pub fn copy_from<'i, I>(&mut self, input: I) -> Status
+    where
+        I: Into<CVec<'i>>,
+    {
+        let input = input.into();
+
+        if input.size() != self.size() {
+            return Err(Error::mismatch_size(self.size(), input.size()));
+        }
+        if !self.has_norm() || !self.has_phas() {
+            return Err(Error::invalid_arg("self", "partial complex vector"));
+        }
+
+        unsafe { ffi::cvec_copy(input.as_ptr(), self.as_mut_ptr()) };
+        Ok(())
+    }
+}
+
+impl<'a> LVecMut<'a> {
+    /**
+     * Set all elements to zero
+     */
+    pub fn zeros(&mut self) {
+        unsafe { ffi::lvec_zeros(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Set all elements to one
+     */
+    pub fn ones(&mut self) {
+        unsafe { ffi::lvec_ones(self.as_mut_ptr()) };
+    }
+}
+
+impl<'a, X> FMat<'a, X> {
+    /**
+     * Compute the product of a matrix by a vector
+     *
+     * - `input` Vector to compute product with (`length` long)
+     * - `output` Vector to store results in (`height` long)
+     */
+    pub fn vecmul<'i, 'o, I, O>(&self, input: I, output: O) -> Status
+    where
+        I: Into<FVec<'i>>,
+        O: Into<FVecMut<'o>>,
+    {
+        let input = input.into();
+        let mut output = output.into();
+
+        if input.size() != self.length() {
+            return Err(Error::mismatch_size(self.length(), input.size()));
+        }
+        if output.size() != self.height() {
+            return Err(Error::mismatch_size(self.height(), output.size()));
+        }
+
+        unsafe { ffi::fmat_vecmul(self.as_ptr(), input.as_ptr(), output.as_mut_ptr()) };
+        Ok(())
+    }
+}
+
+impl<'a, X> FMatMut<'a, X> {
+    /**
+     * Set all elements to a given value
+     */
+    pub fn set_all(&mut self, value: f32) {
+        unsafe { ffi::fmat_set(self.as_mut_ptr(), value) };
+    }
+
+    /**
+     * Set all elements to zero
+     */
+    pub fn zeros(&mut self) {
+        unsafe { ffi::fmat_zeros(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Set all elements to one
+     */
+    pub fn ones(&mut self) {
+        unsafe { ffi::fmat_ones(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Revert order of elements in each row
+     */
+    pub fn rev(&mut self) {
+        unsafe { ffi::fmat_rev(self.as_mut_ptr()) };
+    }
+
+    /**
+     * Apply weight to each row of matrix
+     *
+     * The first row of `weight` is used as weighting coefficients.
+     */
+    pub fn weight<'w, W, Y>(&mut self, weight: W) -> Status
+    where
+        W: Into<FMat<'w, Y>>,
+    {
+        let weight = weight.into();
+
+        if weight.height() < 1 {
+            return Err(Error::mismatch_size(1, weight.height()));
+        }
+
+        unsafe { ffi::fmat_weight(self.as_mut_ptr(), weight.as_ptr()) };
+        Ok(())
+    }
+
+    /**
+     * Copy elements from another matrix of the same size
+     */
+    pub fn copy_from<'i, I, Y>(&mut self, input: I) -> Status
+    where
+        I: Into<FMat<'i, Y>>,
+    {
+        let input = input.into();
+
+        if input.height() != self.height() {
+            return Err(Error::mismatch_size(self.height(), input.height()));
+        }
+        if input.length() != self.length() {
+            return Err(Error::mismatch_size(self.length(), input.length()));
+        }
+
+        unsafe { ffi::fmat_copy(input.as_ptr(), self.as_mut_ptr()) };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{vec::*, *};
+
+    #[test]
+    fn test_fvec_math() {
+        let mut data = [-1.0f32, 4.0, -9.0, 16.0];
+
+        {
+            let mut vec = FVecMut::from(data.as_mut());
+            vec.abs();
+            vec.sqrt();
+            vec.rev();
+        }
+        assert_eq!(data, [4.0, 3.0, 2.0, 1.0]);
+
+        {
+            let mut vec = FVecMut::from(data.as_mut());
+            vec.weight([0.5f32, 0.5].as_ref());
+            vec.pow(2.0);
+        }
+        assert_eq!(data, [4.0, 2.25, 4.0, 1.0]);
+
+        let mut out = [0f32; 4];
+        FVecMut::from(out.as_mut())
+            .copy_from(data.as_ref())
+            .unwrap();
+        assert_eq!(out, data);
+
+        assert_eq!(
+            FVecMut::from(out.as_mut()).copy_from([1f32; 3].as_ref()),
+            Err(Error::MismatchSize {
+                expected: 4,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_cvec_math() {
+        let mut spec = vec::CVecBuf::from_parts([0.0f32, 1.0], [1.0, 1.0]).unwrap();
+
+        spec.as_cvec_mut().logmag(1.0);
+        assert_eq!(spec.norm()[0], 0.0);
+        assert!((spec.norm()[1] - 2f32.ln()).abs() < 1e-6);
+        assert_eq!(spec.phas(), &[1.0, 1.0]);
+
+        let mut norm = [1f32; 2];
+        {
+            let mut norm_only = CVecNormMut::from(norm.as_mut());
+            norm_only.zeros();
+            norm_only.phas_ones();
+        }
+        assert_eq!(norm, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_fmat_math() {
+        let rows: &[&[f32]] = &[&[1.0, 2.0], &[3.0, 4.0], &[5.0, 6.0]];
+        let mat: FMat<_> = rows.into();
+
+        let mut out = [0f32; 3];
+        mat.vecmul([1f32, 1.0].as_ref(), out.as_mut()).unwrap();
+        assert_eq!(out, [3.0, 7.0, 11.0]);
+
+        assert_eq!(
+            mat.vecmul([1f32; 3].as_ref(), out.as_mut()),
+            Err(Error::MismatchSize {
+                expected: 2,
+                actual: 3
+            })
+        );
+
+        let mut buf = vec::FMatBuf::new(3, 2);
+        buf.as_mat_mut().copy_from(rows).unwrap();
+        buf.as_mat_mut().rev();
+        assert_eq!(buf.row(2), &[6.0, 5.0]);
+
+        let weight: &[&[f32]] = &[&[2.0, 0.0]];
+        buf.as_mat_mut().weight(weight).unwrap();
+        assert_eq!(buf.row(0), &[4.0, 0.0]);
     }
 }
@@ -40,7 +40,7 @@ impl<'a> FVec<'a> {
     #[inline]
     pub(crate) fn check_size(&self, min_size: usize) -> Status {
         if self.fvec.length < min_size as _ {
-            Err(Error::MismatchSize)
+            Err(Error::mismatch_size(min_size, self.fvec.length as usize))
         } else {
             Ok(())
         }
@@ -70,7 +70,7 @@ pub struct FVecMut<'a> {
 }
 
 impl<'a> FVecMut<'a> {
-    pub(crate) fn as_mut_ptr(&'a mut self) -> *mut ffi::fvec_t {
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut ffi::fvec_t {
         &mut self.fvec
     }
 
@@ -88,7 +88,7 @@ impl<'a> FVecMut<'a> {
     #[inline]
     pub(crate) fn check_size(&self, min_size: usize) -> Status {
         if self.fvec.length < min_size as _ {
-            Err(Error::MismatchSize)
+            Err(Error::mismatch_size(min_size, self.fvec.length as usize))
         } else {
             Ok(())
         }
@@ -124,7 +124,7 @@ impl<'a> CVec<'a> {
         #[cfg(feature = "check-size")]
         {
             if norm.len() != phas.len() {
-                return Err(Error::MismatchSize);
+                return Err(Error::mismatch_size(norm.len(), phas.len()));
             }
         }
         Ok(Self {
@@ -163,7 +163,10 @@ impl<'a> CVec<'a> {
     #[inline]
     pub(crate) fn check_size(&self, min_size: usize) -> Status {
         if (self.cvec.length - 1) * 2 < min_size as _ {
-            Err(Error::MismatchSize)
+            Err(Error::mismatch_size(
+                min_size,
+                (self.cvec.length as usize - 1) * 2,
+            ))
         } else {
             Ok(())
         }
@@ -194,7 +197,7 @@ impl<'a> CVecMut<'a> {
         #[cfg(feature = "check-size")]
         {
             if norm.len() != phas.len() {
-                return Err(Error::MismatchSize);
+                return Err(Error::mismatch_size(norm.len(), phas.len()));
             }
         }
         Ok(Self {
@@ -229,10 +232,18 @@ impl<'a> CVecMut<'a> {
         }
     }
 
-    pub(crate) fn as_mut_ptr(&'a mut self) -> *mut ffi::cvec_t {
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut ffi::cvec_t {
         &mut self.cvec
     }
 
+    pub(crate) fn has_norm(&self) -> bool {
+        !self.cvec.norm.is_null()
+    }
+
+    pub(crate) fn has_phas(&self) -> bool {
+        !self.cvec.phas.is_null()
+    }
+
     pub fn size(&self) -> usize {
         self.cvec.length as usize
     }
@@ -247,7 +258,10 @@ impl<'a> CVecMut<'a> {
     #[inline]
     pub(crate) fn check_size(&self, min_size: usize) -> Status {
         if (self.cvec.length - 1) * 2 < min_size as _ {
-            Err(Error::MismatchSize)
+            Err(Error::mismatch_size(
+                min_size,
+                (self.cvec.length as usize - 1) * 2,
+            ))
         } else {
             Ok(())
         }
@@ -324,6 +338,237 @@ impl<'a, T: AsMut<[f32]>> From<T> for CVecPhasMut<'a> {
     }
 }
 
+/**
+ * Immutable double precision floating point vector
+ */
+#[repr(transparent)]
+pub struct LVec<'a> {
+    lvec: ffi::lvec_t,
+    _pd: PhantomData<&'a ()>,
+}
+
+impl<'a> LVec<'a> {
+    pub(crate) fn as_ptr(&'a self) -> *const ffi::lvec_t {
+        &self.lvec
+    }
+
+    /**
+     * Create a vector from an already existing `lvec_t` pointer.
+     *
+     * The vector is non-owned; useful to view the coefficients of `Filter`,
+     * for instance.
+     *
+     * # Safety
+     *
+     * - The `ptr` must not be `null`.
+     * - The `ptr` should points to already initialized vector data.
+     */
+    #[allow(clippy::missing_safety_doc)] // TODO: Remove after closing https://github.com/rust-lang/rust-clippy/issues/5593
+    pub unsafe fn from_raw_ptr(ptr: *const ffi::lvec_t) -> Self {
+        Self {
+            lvec: *ptr,
+            _pd: PhantomData,
+        }
+    }
+
+    /**
+     * Get the elements of an already existing `lvec_t` pointer.
+     *
+     * # Safety
+     *
+     * - The `ptr` must not be `null`.
+     * - The `ptr` should points to already initialized vector data.
+     * - The data should outlive the lifetime `'a`.
+     */
+    #[allow(clippy::missing_safety_doc)] // TODO: Remove after closing https://github.com/rust-lang/rust-clippy/issues/5593
+    pub unsafe fn slice_from_raw_ptr(ptr: *const ffi::lvec_t) -> &'a [f64] {
+        std::slice::from_raw_parts((*ptr).data, (*ptr).length as usize)
+    }
+
+    pub fn size(&self) -> usize {
+        self.lvec.length as usize
+    }
+
+    /**
+     * Get the elements of vector
+     *
+     * The elements are borrowed from the vector, so they cannot outlive the data:
+     *
+     * ```compile_fail
+     * use aubio_rs::vec::LVec;
+     *
+     * fn elements(data: &'static [f64]) -> &'static [f64] {
+     *     LVec::from(data).as_slice()
+     * }
+     * ```
+     */
+    pub fn as_slice(&self) -> &[f64] {
+        unsafe { LVec::slice_from_raw_ptr(self.as_ptr()) }
+    }
+
+    #[cfg(not(feature = "check-size"))]
+    #[inline]
+    #[allow(dead_code)]
+    pub(crate) fn check_size(&self, _min_size: usize) -> Status {
+        Ok(())
+    }
+
+    #[cfg(feature = "check-size")]
+    #[inline]
+    #[allow(dead_code)]
+    pub(crate) fn check_size(&self, min_size: usize) -> Status {
+        if self.lvec.length < min_size as _ {
+            Err(Error::mismatch_size(min_size, self.lvec.length as usize))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'a> From<&'a [f64]> for LVec<'a> {
+    fn from(data: &'a [f64]) -> Self {
+        Self {
+            lvec: ffi::lvec_t {
+                length: data.len() as ffi::uint_t,
+                data: data.as_ptr() as *mut _,
+            },
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<'a, const N: usize> From<&'a [f64; N]> for LVec<'a> {
+    fn from(data: &'a [f64; N]) -> Self {
+        data.as_ref().into()
+    }
+}
+
+impl<'a> From<&'a Vec<f64>> for LVec<'a> {
+    fn from(data: &'a Vec<f64>) -> Self {
+        data.as_slice().into()
+    }
+}
+
+/**
+ * Mutable double precision floating point vector
+ */
+#[repr(transparent)]
+pub struct LVecMut<'a> {
+    lvec: ffi::lvec_t,
+    _pd: PhantomData<&'a mut ()>,
+}
+
+impl<'a> LVecMut<'a> {
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut ffi::lvec_t {
+        &mut self.lvec
+    }
+
+    /**
+     * Create a mutable vector from an already existing `lvec_t` pointer.
+     *
+     * The vector is non-owned; useful to edit the coefficients of `Filter`,
+     * for instance.
+     *
+     * # Safety
+     *
+     * - The `ptr` must not be `null`.
+     * - The `ptr` should points to already initialized vector data.
+     * - The data should not be accessed through other pointers while the vector is alive.
+     */
+    #[allow(clippy::missing_safety_doc)] // TODO: Remove after closing https://github.com/rust-lang/rust-clippy/issues/5593
+    pub unsafe fn from_raw_ptr(ptr: *mut ffi::lvec_t) -> Self {
+        Self {
+            lvec: *ptr,
+            _pd: PhantomData,
+        }
+    }
+
+    /**
+     * Get the mutable elements of an already existing `lvec_t` pointer.
+     *
+     * # Safety
+     *
+     * - The `ptr` must not be `null`.
+     * - The `ptr` should points to already initialized vector data.
+     * - The data should outlive the lifetime `'a` and should not be accessed
+     *   through other pointers during it.
+     */
+    #[allow(clippy::missing_safety_doc)] // TODO: Remove after closing https://github.com/rust-lang/rust-clippy/issues/5593
+    pub unsafe fn slice_from_raw_ptr(ptr: *mut ffi::lvec_t) -> &'a mut [f64] {
+        std::slice::from_raw_parts_mut((*ptr).data, (*ptr).length as usize)
+    }
+
+    pub fn size(&self) -> usize {
+        self.lvec.length as usize
+    }
+
+    /**
+     * Get the elements of vector
+     */
+    pub fn as_slice(&self) -> &[f64] {
+        unsafe { std::slice::from_raw_parts(self.lvec.data, self.size()) }
+    }
+
+    /**
+     * Get the mutable elements of vector
+     *
+     * The elements are borrowed from the vector, so they cannot outlive the data:
+     *
+     * ```compile_fail
+     * use aubio_rs::vec::LVecMut;
+     *
+     * fn elements(data: &'static mut [f64]) -> &'static mut [f64] {
+     *     LVecMut::from(data).as_mut_slice()
+     * }
+     * ```
+     */
+    pub fn as_mut_slice(&mut self) -> &mut [f64] {
+        unsafe { std::slice::from_raw_parts_mut(self.lvec.data, self.size()) }
+    }
+
+    #[cfg(not(feature = "check-size"))]
+    #[inline]
+    #[allow(dead_code)]
+    pub(crate) fn check_size(&self, _min_size: usize) -> Status {
+        Ok(())
+    }
+
+    #[cfg(feature = "check-size")]
+    #[inline]
+    #[allow(dead_code)]
+    pub(crate) fn check_size(&self, min_size: usize) -> Status {
+        if self.lvec.length < min_size as _ {
+            Err(Error::mismatch_size(min_size, self.lvec.length as usize))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'a> From<&'a mut [f64]> for LVecMut<'a> {
+    fn from(data: &'a mut [f64]) -> Self {
+        Self {
+            lvec: ffi::lvec_t {
+                length: data.len() as ffi::uint_t,
+                data: data.as_mut_ptr(),
+            },
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<'a, const N: usize> From<&'a mut [f64; N]> for LVecMut<'a> {
+    fn from(data: &'a mut [f64; N]) -> Self {
+        data.as_mut().into()
+    }
+}
+
+impl<'a> From<&'a mut Vec<f64>> for LVecMut<'a> {
+    fn from(data: &'a mut Vec<f64>) -> Self {
+        data.as_mut_slice().into()
+    }
+}
+
 /**
  * Immutable matrix of real valued data.
  */
@@ -349,8 +594,11 @@ impl<'a, X> FMat<'a, X> {
 
     /// Read sample value in a buffer
     pub fn get_sample(&self, channel: usize, position: usize) -> Result<f32> {
-        if channel >= self.height() || position >= self.length() {
-            return Err(Error::InvalidArg);
+        if channel >= self.height() {
+            return Err(Error::invalid_arg("channel", channel));
+        }
+        if position >= self.length() {
+            return Err(Error::invalid_arg("position", position));
         }
         Ok(unsafe {
             ffi::fmat_get_sample(
@@ -438,6 +686,391 @@ impl<'a, T: AsRef<[&'a [f32]]>> From<T> for FMat<'a, FMatVecs> {
     }
 }
 
+/**
+ * Mutable matrix of real valued data.
+ */
+#[repr(C)]
+pub struct FMatMut<'a, X> {
+    fmat: ffi::fmat_t,
+    _x: X,
+    _pd: PhantomData<&'a mut ()>,
+}
+
+impl<'a, X> FMatMut<'a, X> {
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut ffi::fmat_t {
+        &mut self.fmat
+    }
+
+    pub fn length(&self) -> usize {
+        self.fmat.length as usize
+    }
+
+    pub fn height(&self) -> usize {
+        self.fmat.height as usize
+    }
+
+    #[cfg(not(feature = "check-size"))]
+    #[inline]
+    pub(crate) fn check_size(&self, _min_length: usize) -> Status {
+        Ok(())
+    }
+
+    #[cfg(feature = "check-size")]
+    #[inline]
+    pub(crate) fn check_size(&self, min_length: usize) -> Status {
+        if self.fmat.length < min_length as _ {
+            Err(Error::mismatch_size(min_length, self.fmat.length as usize))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+pub type FMatVecsMut = Vec<*mut f32>;
+
+impl<'a, T: AsMut<[&'a mut [f32]]>> From<T> for FMatMut<'a, FMatVecsMut> {
+    /**
+     * Create a mutable matrix from a slices of channels
+     *
+     * Matrix's horizontal height is the number of slices, and
+     * its vertical length the slice's len.
+     */
+    fn from(mut data: T) -> Self {
+        let data = data.as_mut();
+
+        #[cfg(feature = "check-size")]
+        {
+            let mut vecs = data.iter();
+            if let Some(fst) = vecs.next() {
+                let len = fst.len();
+                if len == 0 {
+                    panic!("No values in slice");
+                }
+                if vecs.any(|nxt| nxt.len() != len) {
+                    panic!("Slices have different lengths");
+                }
+            } else {
+                panic!("No slices in vec");
+            }
+        }
+
+        let length = data.first().map(|v| v.len()).unwrap_or(0);
+        let mut array = data.iter_mut().map(|v| v.as_mut_ptr()).collect::<Vec<_>>();
+
+        Self {
+            fmat: ffi::fmat_t {
+                height: array.len() as _,
+                length: length as _,
+                data: array.as_mut_ptr(),
+            },
+            _x: array,
+            _pd: PhantomData,
+        }
+    }
+}
+
+/**
+ * Owned floating point vector
+ *
+ * The data is allocated on heap. Use `as_vec()` and `as_vec_mut()` to get views which
+ * can be passed to processing functions, or pass the reference to buffer directly.
+ */
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FVecBuf {
+    data: Vec<f32>,
+}
+
+impl FVecBuf {
+    /**
+     * Create zero-filled vector
+     *
+     * - `size` The number of elements
+     */
+    pub fn new(size: usize) -> Self {
+        Self {
+            data: vec![0.0; size],
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    /**
+     * Get immutable view of vector
+     */
+    pub fn as_vec(&self) -> FVec<'_> {
+        self.data.as_slice().into()
+    }
+
+    /**
+     * Get mutable view of vector
+     */
+    pub fn as_vec_mut(&mut self) -> FVecMut<'_> {
+        self.data.as_mut_slice().into()
+    }
+
+    /**
+     * Unwrap the underlying data
+     */
+    pub fn into_inner(self) -> Vec<f32> {
+        self.data
+    }
+}
+
+impl From<Vec<f32>> for FVecBuf {
+    fn from(data: Vec<f32>) -> Self {
+        Self { data }
+    }
+}
+
+impl From<FVecBuf> for Vec<f32> {
+    fn from(buf: FVecBuf) -> Self {
+        buf.data
+    }
+}
+
+impl AsRef<[f32]> for FVecBuf {
+    fn as_ref(&self) -> &[f32] {
+        &self.data
+    }
+}
+
+impl AsMut<[f32]> for FVecBuf {
+    fn as_mut(&mut self) -> &mut [f32] {
+        &mut self.data
+    }
+}
+
+impl Deref for FVecBuf {
+    type Target = [f32];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl DerefMut for FVecBuf {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+/**
+ * Owned complex floating point vector
+ *
+ * The norm and phas parts are stored in a single heap allocated buffer one after another.
+ * Use `as_cvec()` and `as_cvec_mut()` to get views which can be passed to processing functions,
+ * or pass the reference to buffer directly.
+ */
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CVecBuf {
+    data: Vec<f32>,
+}
+
+impl CVecBuf {
+    /**
+     * Create zero-filled complex vector for spectrum of given window size
+     *
+     * - `win_size` The size of window (the norm and phas parts will be `win_size / 2 + 1` long)
+     */
+    pub fn new(win_size: usize) -> Self {
+        Self {
+            data: vec![0.0; (win_size / 2 + 1) * 2],
+        }
+    }
+
+    /**
+     * Create complex vector from norm and phas parts
+     */
+    pub fn from_parts<T: AsRef<[f32]>>(norm: T, phas: T) -> Result<Self> {
+        let norm = norm.as_ref();
+        let phas = phas.as_ref();
+        if norm.len() != phas.len() {
+            return Err(Error::mismatch_size(norm.len(), phas.len()));
+        }
+        let mut data = Vec::with_capacity(norm.len() * 2);
+        data.extend_from_slice(norm);
+        data.extend_from_slice(phas);
+        Ok(Self { data })
+    }
+
+    pub fn size(&self) -> usize {
+        self.data.len() / 2
+    }
+
+    pub fn norm(&self) -> &[f32] {
+        &self.data[..self.size()]
+    }
+
+    pub fn norm_mut(&mut self) -> &mut [f32] {
+        let size = self.size();
+        &mut self.data[..size]
+    }
+
+    pub fn phas(&self) -> &[f32] {
+        &self.data[self.size()..]
+    }
+
+    pub fn phas_mut(&mut self) -> &mut [f32] {
+        let size = self.size();
+        &mut self.data[size..]
+    }
+
+    /**
+     * Get immutable view of complex vector
+     */
+    pub fn as_cvec(&self) -> CVec<'_> {
+        self.data.as_slice().into()
+    }
+
+    /**
+     * Get mutable view of complex vector
+     */
+    pub fn as_cvec_mut(&mut self) -> CVecMut<'_> {
+        self.data.as_mut_slice().into()
+    }
+}
+
+impl AsRef<[f32]> for CVecBuf {
+    fn as_ref(&self) -> &[f32] {
+        &self.data
+    }
+}
+
+impl AsMut<[f32]> for CVecBuf {
+    fn as_mut(&mut self) -> &mut [f32] {
+        &mut self.data
+    }
+}
+
+/**
+ * Owned matrix of real valued data
+ *
+ * The rows are stored in a single heap allocated buffer one after another.
+ * Use `as_mat()` and `as_mat_mut()` to get views which can be passed to processing functions,
+ * or pass the reference to buffer directly.
+ */
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FMatBuf {
+    data: Vec<f32>,
+    length: usize,
+    height: usize,
+}
+
+impl FMatBuf {
+    /**
+     * Create zero-filled matrix
+     *
+     * - `height` The number of rows (channels)
+     * - `length` The number of columns (samples)
+     */
+    pub fn new(height: usize, length: usize) -> Self {
+        Self {
+            data: vec![0.0; height * length],
+            length,
+            height,
+        }
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /**
+     * Get row of matrix
+     */
+    pub fn row(&self, index: usize) -> &[f32] {
+        &self.data[index * self.length..(index + 1) * self.length]
+    }
+
+    /**
+     * Get mutable row of matrix
+     */
+    pub fn row_mut(&mut self, index: usize) -> &mut [f32] {
+        &mut self.data[index * self.length..(index + 1) * self.length]
+    }
+
+    /**
+     * Get iterator over rows
+     */
+    pub fn rows(&self) -> impl Iterator<Item = &[f32]> {
+        (0..self.height).map(move |index| self.row(index))
+    }
+
+    /**
+     * Get iterator over mutable rows
+     */
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [f32]> {
+        let length = self.length;
+        let mut rest = &mut self.data[..];
+
+        (0..self.height).map(move |_| {
+            let (row, tail) = std::mem::take(&mut rest).split_at_mut(length);
+            rest = tail;
+            row
+        })
+    }
+
+    /**
+     * Get immutable view of matrix
+     */
+    pub fn as_mat(&self) -> FMat<'_, FMatVecs> {
+        self.into()
+    }
+
+    /**
+     * Get mutable view of matrix
+     */
+    pub fn as_mat_mut(&mut self) -> FMatMut<'_, FMatVecsMut> {
+        self.into()
+    }
+}
+
+impl<'a> From<&'a FMatBuf> for FMat<'a, FMatVecs> {
+    fn from(buf: &'a FMatBuf) -> Self {
+        // Exactly `height` row pointers, even when rows are empty
+        let base = buf.data.as_ptr();
+        let array = (0..buf.height)
+            .map(|index| base.wrapping_add(index * buf.length))
+            .collect::<Vec<_>>();
+
+        Self {
+            fmat: ffi::fmat_t {
+                height: buf.height as _,
+                length: buf.length as _,
+                data: array.as_ptr() as _,
+            },
+            _x: array,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<'a> From<&'a mut FMatBuf> for FMatMut<'a, FMatVecsMut> {
+    fn from(buf: &'a mut FMatBuf) -> Self {
+        let (height, length) = (buf.height, buf.length);
+        let base = buf.data.as_mut_ptr();
+        let mut array = (0..height)
+            .map(|index| base.wrapping_add(index * length))
+            .collect::<Vec<_>>();
+
+        Self {
+            fmat: ffi::fmat_t {
+                height: height as _,
+                length: length as _,
+                data: array.as_mut_ptr(),
+            },
+            _x: array,
+            _pd: PhantomData,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -480,7 +1113,146 @@ mod test {
         let x: &[&[f32]] = &[&[1.0, 2.0], &[4.0, 5.0], &[7.0, 8.0]];
         let fmat: FMat<_> = x.into();
 
-        assert_eq!(Err(Error::InvalidArg), fmat.get_sample(70, 80));
+        assert_eq!(
+            Err(Error::InvalidArg {
+                name: "channel",
+                value: "70".into()
+            }),
+            fmat.get_sample(70, 80)
+        );
+        assert_eq!(
+            Err(Error::InvalidArg {
+                name: "position",
+                value: "80".into()
+            }),
+            fmat.get_sample(0, 80)
+        );
+    }
+
+    #[test]
+    fn test_from_fmat_mut() {
+        let mut a = [1.0, 2.0];
+        let mut b = [4.0, 5.0];
+        let mut c = [7.0, 8.0];
+        let fmat: FMatMut<_> = [a.as_mut(), b.as_mut(), c.as_mut()].into();
+        assert_eq!(2, fmat.length());
+        assert_eq!(3, fmat.height());
+    }
+
+    #[test]
+    fn test_fvec_buf() {
+        let mut buf = FVecBuf::new(4);
+        assert_eq!(buf.size(), 4);
+        buf[1] = 2.0;
+        assert_eq!(buf.as_vec().size(), 4);
+        assert_eq!(buf.as_vec_mut().size(), 4);
+        assert_eq!(Vec::from(buf), vec![0.0, 2.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_cvec_buf() {
+        let mut buf = CVecBuf::new(8);
+        assert_eq!(buf.size(), 5);
+        buf.norm_mut()[0] = 1.0;
+        buf.phas_mut()[0] = 2.0;
+
+        let cvec = buf.as_cvec();
+        assert_eq!(cvec.size(), 5);
+        assert_eq!(cvec.norm(), &[1.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(cvec.phas(), &[2.0, 0.0, 0.0, 0.0, 0.0]);
+
+        let buf = CVecBuf::from_parts([1.0, 2.0], [3.0, 4.0]).unwrap();
+        assert_eq!(buf.norm(), &[1.0, 2.0]);
+        assert_eq!(buf.phas(), &[3.0, 4.0]);
+        assert!(CVecBuf::from_parts(&[1.0][..], &[3.0, 4.0][..]).is_err());
+    }
+
+    #[test]
+    fn test_fmat_buf() {
+        let mut buf = FMatBuf::new(3, 2);
+        buf.row_mut(1).copy_from_slice(&[4.0, 5.0]);
+        assert_eq!(buf.rows().count(), 3);
+
+        let fmat = buf.as_mat();
+        assert_eq!(2, fmat.length());
+        assert_eq!(3, fmat.height());
+        assert_eq!(fmat.get_vec(), vec![&[0.0, 0.0], &[4.0, 5.0], &[0.0, 0.0]]);
+
+        let fmat = buf.as_mat_mut();
+        assert_eq!(2, fmat.length());
+        assert_eq!(3, fmat.height());
+    }
+
+    #[test]
+    fn test_fmat_buf_empty_rows() {
+        let mut buf = FMatBuf::new(3, 0);
+        assert_eq!(buf.rows().count(), 3);
+        assert_eq!(buf.rows_mut().count(), 3);
+
+        let fmat = buf.as_mat();
+        assert_eq!(0, fmat.length());
+        assert_eq!(3, fmat.height());
+        assert_eq!(fmat.get_vec(), vec![&[] as &[f32]; 3]);
+
+        let fmat = buf.as_mat_mut();
+        assert_eq!(0, fmat.length());
+        assert_eq!(3, fmat.height());
+    }
+
+    #[test]
+    fn test_lvec() {
+        let data = [1.0f64, 2.0, 3.0];
+        let lvec: LVec = data.as_ref().into();
+        assert_eq!(lvec.size(), 3);
+        assert_eq!(lvec.as_slice(), &[1.0, 2.0, 3.0]);
+
+        let non_owned = unsafe { LVec::from_raw_ptr(&lvec.lvec) };
+        assert_eq!(non_owned.as_slice(), data.as_ref());
+
+        let mut data = vec![0.0f64; 2];
+        let mut lvec = LVecMut::from(data.as_mut_slice());
+        lvec.as_mut_slice()[1] = 0.5;
+        assert_eq!(lvec.as_slice(), &[0.0, 0.5]);
+        let elements = unsafe { LVecMut::slice_from_raw_ptr(&mut lvec.lvec) };
+        elements[0] = 0.25;
+        assert_eq!(
+            unsafe { LVec::slice_from_raw_ptr(&lvec.lvec) },
+            &[0.25, 0.5]
+        );
+        assert_eq!(data, [0.25, 0.5]);
+
+        let data = vec![1.0f64, 2.0];
+        assert_eq!(LVec::from(&data).as_slice(), &[1.0, 2.0]);
+        assert_eq!(LVec::from(&[3.0f64, 4.0]).size(), 2);
+
+        let mut data = [0.0f64; 2];
+        LVecMut::from(&mut data).as_mut_slice()[0] = 1.0;
+        assert_eq!(data, [1.0, 0.0]);
+    }
+
+    #[cfg(feature = "check-size")]
+    #[test]
+    fn test_lvec_check_size() {
+        let data = [1.0f64, 2.0];
+        let lvec = LVec::from(&data);
+        assert_eq!(lvec.check_size(2), Ok(()));
+        assert_eq!(
+            lvec.check_size(3),
+            Err(Error::MismatchSize {
+                expected: 3,
+                actual: 2
+            })
+        );
+
+        let mut data = [0.0f64; 2];
+        let lvec = LVecMut::from(&mut data);
+        assert_eq!(
+            lvec.check_size(4),
+            Err(Error::MismatchSize {
+                expected: 4,
+                actual: 2
+            })
+        );
     }
 
     #[test]
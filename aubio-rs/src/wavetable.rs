@@ -0,0 +1,293 @@
+use crate::{
+    check_init, ffi,
+    vec::{FMat, FMatMut, FMatVecs, FMatVecsMut, FVec, FVecMut},
+    Error, Result, Status,
+};
+
+/**
+ * Wavetable synthesis object
+ *
+ * This object generates a sine wave using a wavetable.
+ *
+ * Changes of frequency and amplitude are smoothed over several samples
+ * to avoid clicks.
+ */
+pub struct Wavetable {
+    wavetable: *mut ffi::aubio_wavetable_t,
+    hop_size: usize,
+}
+
+impl Drop for Wavetable {
+    fn drop(&mut self) {
+        unsafe { ffi::del_aubio_wavetable(self.wavetable) }
+    }
+}
+
+unsafe impl Send for Wavetable {}
+
+impl Wavetable {
+    /**
+     * Create new wavetable object
+     *
+     * - `sample_rate` Sampling rate of the new wavetable
+     * - `hop_size` Block size of the new wavetable
+     */
+    pub fn new(sample_rate: u32, hop_size: usize) -> Result<Self> {
+        let wavetable = check_init(|| unsafe {
+            ffi::new_aubio_wavetable(sample_rate as ffi::uint_t, hop_size as ffi::uint_t)
+        })?;
+
+        Ok(Self {
+            wavetable,
+            hop_size,
+        })
+    }
+
+    /**
+     * Set wavetable frequency
+     */
+    pub fn with_freq(mut self, freq: f32) -> Result<Self> {
+        self.set_freq(freq).map(|_| self)
+    }
+
+    /**
+     * Set wavetable amplitude
+     */
+    pub fn with_amp(mut self, amp: f32) -> Result<Self> {
+        self.set_amp(amp).map(|_| self)
+    }
+
+    /**
+     * Get hop size
+     */
+    pub fn get_hop(&self) -> usize {
+        self.hop_size
+    }
+
+    /**
+     * Process wavetable
+     *
+     * - `input` Input samples to be added to the output (at least as long as output)
+     * - `output` Output samples
+     *
+     * The output is overwritten by the generated wave, then the input is added to it.
+     */
+    pub fn do_<'i, 'o, I, O>(&mut self, input: I, output: O) -> Status
+    where
+        I: Into<FVec<'i>>,
+        O: Into<FVecMut<'o>>,
+    {
+        let input = input.into();
+        let mut output = output.into();
+
+        if input.size() < output.size() {
+            return Err(Error::mismatch_size(output.size(), input.size()));
+        }
+
+        unsafe { ffi::aubio_wavetable_do(self.wavetable, input.as_ptr(), output.as_mut_ptr()) }
+        Ok(())
+    }
+
+    /**
+     * Generate wave into output
+     *
+     * - `output` Output samples
+     *
+     * The output is overwritten by the generated wave.
+     */
+    pub fn generate<'o, O>(&mut self, output: O)
+    where
+        O: Into<FVecMut<'o>>,
+    {
+        let mut output = output.into();
+
+        unsafe { ffi::aubio_wavetable_do(self.wavetable, std::ptr::null(), output.as_mut_ptr()) }
+    }
+
+    /**
+     * Process wavetable, multiple channels
+     *
+     * - `input` Input samples to be added to the output (same dimensions as output or bigger)
+     * - `output` Output samples
+     *
+     * Each channel of output receives the same generated wave, then the input is added to it.
+     */
+    pub fn do_multi<'i, 'o, I, O>(&mut self, input: I, output: O) -> Status
+    where
+        I: Into<FMat<'i, FMatVecs>>,
+        O: Into<FMatMut<'o, FMatVecsMut>>,
+    {
+        let input = input.into();
+        let mut output = output.into();
+
+        if input.length() < output.length() {
+            return Err(Error::mismatch_size(output.length(), input.length()));
+        }
+        if input.height() < output.height() {
+            return Err(Error::mismatch_size(output.height(), input.height()));
+        }
+
+        unsafe {
+            ffi::aubio_wavetable_do_multi(self.wavetable, input.as_ptr(), output.as_mut_ptr())
+        }
+        Ok(())
+    }
+
+    /**
+     * Get current playing state
+     */
+    pub fn get_playing(&self) -> bool {
+        0 != unsafe { ffi::aubio_wavetable_get_playing(self.wavetable) }
+    }
+
+    /**
+     * Set current playing state
+     */
+    pub fn set_playing(&mut self, playing: bool) -> Status {
+        if 0 == unsafe { ffi::aubio_wavetable_set_playing(self.wavetable, playing as ffi::uint_t) }
+        {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("playing", playing))
+        }
+    }
+
+    /**
+     * Play wavetable
+     */
+    pub fn play(&mut self) -> Status {
+        if 0 == unsafe { ffi::aubio_wavetable_play(self.wavetable) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("playing", true))
+        }
+    }
+
+    /**
+     * Stop wavetable
+     */
+    pub fn stop(&mut self) -> Status {
+        if 0 == unsafe { ffi::aubio_wavetable_stop(self.wavetable) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("playing", false))
+        }
+    }
+
+    /**
+     * Set wavetable frequency, in Hz
+     *
+     * The frequency should be between 0 and half of the sampling rate.
+     */
+    pub fn set_freq(&mut self, freq: f32) -> Status {
+        if 0 == unsafe { ffi::aubio_wavetable_set_freq(self.wavetable, freq) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("freq", freq))
+        }
+    }
+
+    /**
+     * Get wavetable frequency, in Hz
+     */
+    pub fn get_freq(&self) -> f32 {
+        unsafe { ffi::aubio_wavetable_get_freq(self.wavetable) }
+    }
+
+    /**
+     * Set wavetable amplitude
+     *
+     * The amplitude should be between 0 and 1.
+     */
+    pub fn set_amp(&mut self, amp: f32) -> Status {
+        if 0 == unsafe { ffi::aubio_wavetable_set_amp(self.wavetable, amp) } {
+            Ok(())
+        } else {
+            Err(Error::invalid_arg("amp", amp))
+        }
+    }
+
+    /**
+     * Get wavetable amplitude
+     */
+    pub fn get_amp(&self) -> f32 {
+        unsafe { ffi::aubio_wavetable_get_amp(self.wavetable) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    const SAMPLERATE: u32 = 44100;
+    const HOP_S: usize = 256;
+
+    #[test]
+    fn test() {
+        let mut wavetable = Wavetable::new(SAMPLERATE, HOP_S)
+            .unwrap()
+            .with_freq(440.0)
+            .unwrap()
+            .with_amp(0.5)
+            .unwrap();
+
+        assert_eq!(wavetable.get_hop(), HOP_S);
+        assert!(!wavetable.get_playing());
+
+        let input = [0f32; HOP_S];
+        let mut output = [0f32; HOP_S];
+
+        wavetable.play().unwrap();
+        assert!(wavetable.get_playing());
+
+        for _ in 0..4 {
+            wavetable.do_(input.as_ref(), output.as_mut()).unwrap();
+        }
+        wavetable.generate(output.as_mut());
+        assert!((wavetable.get_freq() - 440.0).abs() < 1e-3);
+        assert!((wavetable.get_amp() - 0.5).abs() < 1e-3);
+        assert!(output.iter().any(|s| s.abs() > 0.1));
+        assert!(output.iter().all(|s| s.abs() <= 0.5 + 1e-3));
+
+        let mut left = [0f32; HOP_S];
+        let mut right = [0f32; HOP_S];
+        let block: &[&[f32]] = &[&input, &input];
+        wavetable
+            .do_multi(block, &mut [left.as_mut(), right.as_mut()])
+            .unwrap();
+        assert!(left.iter().any(|s| s.abs() > 0.1));
+
+        wavetable.stop().unwrap();
+        assert!(!wavetable.get_playing());
+    }
+
+    #[test]
+    fn test_wrong_params() {
+        let mut wavetable = Wavetable::new(SAMPLERATE, HOP_S).unwrap();
+
+        assert_eq!(
+            wavetable.set_freq(-1.0),
+            Err(Error::InvalidArg {
+                name: "freq",
+                value: "-1".into()
+            })
+        );
+        assert_eq!(
+            wavetable.set_amp(2.0),
+            Err(Error::InvalidArg {
+                name: "amp",
+                value: "2".into()
+            })
+        );
+
+        let input = [0f32; HOP_S / 2];
+        let mut output = [0f32; HOP_S];
+        assert_eq!(
+            wavetable.do_(input.as_ref(), output.as_mut()),
+            Err(Error::MismatchSize {
+                expected: HOP_S,
+                actual: HOP_S / 2
+            })
+        );
+    }
+}
@@ -87,11 +87,13 @@ impl FromStr for WindowType {
             "gaussian" => Gaussian,
             "welch" => Welch,
             "parzen" => Parzen,
-            _ => return Err(Error::InvalidArg),
+            _ => return Err(Error::invalid_arg("WindowType", src)),
         })
     }
 }
 
+impl_serde_str!(WindowType);
+
 impl WindowType {
     /**
      * Set elements of a vector to window coefficients
@@ -103,4 +105,86 @@ impl WindowType {
         let mut window = window.into();
         unsafe { ffi::fvec_set_window(window.as_mut_ptr(), self.as_native_cstr() as *mut _) };
     }
+
+    /**
+     * Generate window coefficients
+     *
+     * The coefficients are the same as the ones used by `PVoc` for this window type.
+     */
+    pub fn generate(self, size: usize) -> Vec<f32> {
+        let mut window = vec![0f32; size];
+        self.set(window.as_mut_slice());
+        window
+    }
+
+    /**
+     * Compute the energy of window of given size (the sum of squared coefficients)
+     */
+    pub fn energy(self, size: usize) -> f32 {
+        self.generate(size).iter().map(|c| c * c).sum()
+    }
+
+    /**
+     * Compute the overlap-add gain of window of given size for a given hop size
+     *
+     * This is the mean value of the sum of overlapping windows placed every `hop_size` samples,
+     * i.e. the gain to compensate when resynthesising a signal from windowed frames.
+     */
+    pub fn overlap_add_gain(self, size: usize, hop_size: usize) -> f32 {
+        self.generate(size).iter().sum::<f32>() / hop_size as f32
+    }
+}
+
+impl<'a> FVecMut<'a> {
+    /**
+     * Set elements to window coefficients
+     */
+    pub fn set_window(&mut self, window_type: WindowType) {
+        unsafe { ffi::fvec_set_window(self.as_mut_ptr(), window_type.as_native_cstr() as *mut _) };
+    }
+
+    /**
+     * Multiply elements by window coefficients
+     */
+    pub fn apply_window(&mut self, window_type: WindowType) {
+        let window = window_type.generate(self.size());
+        self.weight(window.as_slice());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn test_generate() {
+        let window = WindowType::Hanningz.generate(8);
+        assert_eq!(window.len(), 8);
+        assert_eq!(window[0], 0.0);
+        assert!((window[4] - 1.0).abs() < 1e-6);
+
+        let ones = WindowType::Ones.generate(4);
+        assert_eq!(ones, [1.0; 4]);
+        assert_eq!(WindowType::Ones.energy(4), 4.0);
+        assert_eq!(WindowType::Ones.overlap_add_gain(4, 2), 2.0);
+
+        // hanning windows sum up to half their size with 50% overlap
+        assert!((WindowType::Hanningz.overlap_add_gain(512, 256) - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_apply_window() {
+        let mut frame = [2f32; 8];
+        let mut frame_vec = vec::FVecMut::from(frame.as_mut());
+        frame_vec.apply_window(WindowType::Hanningz);
+
+        let window = WindowType::Hanningz.generate(8);
+        for (f, w) in frame.iter().zip(window.iter()) {
+            assert!((f - 2.0 * w).abs() < 1e-6);
+        }
+
+        let mut frame = [0f32; 8];
+        vec::FVecMut::from(frame.as_mut()).set_window(WindowType::Hanningz);
+        assert_eq!(frame.as_ref(), window.as_slice());
+    }
 }